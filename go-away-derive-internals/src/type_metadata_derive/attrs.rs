@@ -0,0 +1,114 @@
+use syn::{spanned::Spanned, Lit, Meta, NestedMeta};
+
+/// The `#[go_away(...)]` attributes that can be put on a field
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// A type to describe the field as, instead of the fields rust type
+    pub as_type: Option<syn::Type>,
+    /// Extra struct tags for go output, from `#[go_away(tag(key = "value"))]`
+    pub tags: Vec<(String, String)>,
+}
+
+impl FieldAttrs {
+    pub fn from_ast(field: &syn::Field) -> Result<FieldAttrs, syn::Error> {
+        let mut rv = FieldAttrs::default();
+
+        for meta in go_away_metas(&field.attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("as") => {
+                    let Lit::Str(lit) = &name_value.lit else {
+                        return Err(syn::Error::new_spanned(
+                            &name_value.lit,
+                            "expected go_away(as = \"...\") to be a string",
+                        ));
+                    };
+                    rv.as_type = Some(lit.parse()?);
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("tag") => {
+                    for nested in list.nested {
+                        let NestedMeta::Meta(Meta::NameValue(name_value)) = &nested else {
+                            return Err(syn::Error::new_spanned(
+                                nested,
+                                "expected go_away(tag(...)) to contain key = \"value\" pairs",
+                            ));
+                        };
+                        let (Some(key), Lit::Str(value)) =
+                            (name_value.path.get_ident(), &name_value.lit)
+                        else {
+                            return Err(syn::Error::new_spanned(
+                                name_value,
+                                "expected go_away(tag(...)) to contain key = \"value\" pairs",
+                            ));
+                        };
+                        rv.tags.push((key.to_string(), value.value()));
+                    }
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown go_away field attribute",
+                    ))
+                }
+            }
+        }
+
+        Ok(rv)
+    }
+}
+
+/// The `#[go_away(...)]` attributes that can be put on a struct or enum
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// A name to use for the type instead of its rust name
+    pub rename: Option<String>,
+}
+
+impl ContainerAttrs {
+    pub fn from_ast(input: &syn::DeriveInput) -> Result<ContainerAttrs, syn::Error> {
+        let mut rv = ContainerAttrs::default();
+
+        for meta in go_away_metas(&input.attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("rename") =>
+                {
+                    let Lit::Str(lit) = &name_value.lit else {
+                        return Err(syn::Error::new_spanned(
+                            &name_value.lit,
+                            "expected go_away(rename = \"...\") to be a string",
+                        ));
+                    };
+                    rv.rename = Some(lit.value());
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unknown go_away container attribute",
+                    ))
+                }
+            }
+        }
+
+        Ok(rv)
+    }
+}
+
+/// Finds all the `#[go_away(...)]` attributes in `attrs` and returns their contents
+fn go_away_metas(attrs: &[syn::Attribute]) -> Result<Vec<NestedMeta>, syn::Error> {
+    let mut rv = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("go_away") {
+            continue;
+        }
+        match attr.parse_meta()? {
+            Meta::List(list) => rv.extend(list.nested),
+            other => {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "expected go_away attribute to be of the form #[go_away(...)]",
+                ))
+            }
+        }
+    }
+    Ok(rv)
+}
@@ -5,18 +5,26 @@ use serde_derive_internals::{
     attr::TagType,
     Ctxt,
 };
+use syn::ext::IdentExt;
 
+mod attrs;
+mod rename_all_fields;
 mod type_id;
+mod validate;
 
+use attrs::{ContainerAttrs, FieldAttrs};
 use type_id::TypeIdCall;
 
 pub fn type_metadata_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::Error> {
     use quote::TokenStreamExt;
 
+    validate::validate_input(ast)?;
+    let ast = &*rename_all_fields::desugar(ast)?;
+    let attrs = ContainerAttrs::from_ast(ast)?;
+
     let ctx = Ctxt::new();
 
-    let container =
-        Container::from_ast(&ctx, ast, serde_derive_internals::Derive::Deserialize).unwrap();
+    let container = Container::from_ast(&ctx, ast, serde_derive_internals::Derive::Deserialize);
 
     match ctx.check() {
         Ok(_) => {}
@@ -29,13 +37,39 @@ pub fn type_metadata_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::
             return Ok(rv);
         }
     }
+    let container = container
+        .ok_or_else(|| syn::Error::new_spanned(&ast.ident, "go_away couldn't parse this type"))?;
+    validate::validate_container(&container, &attrs)?;
+
     let type_id = TypeIdCall::for_struct(&container.ident, container.generics);
 
     let ident = &container.ident;
-    let name_literal = Literal::string(&ident.to_string());
+    let name = attrs.rename.unwrap_or_else(|| ident.unraw().to_string());
+    let name_literal = Literal::string(&name);
     let mut inner = TokenStream::new();
+
+    if let Some(metadata_call) = wire_type_metadata_call(&container)? {
+        // serde serializes this type as some other type, so we register it as a newtype
+        // of that type rather than using its rust shape.
+        inner.append_all(quote! {
+            let nt = types::NewType {
+                name: #name_literal.to_string(),
+                inner: #metadata_call,
+            };
+            registry.register_newtype(#type_id, nt)
+        });
+
+        let type_ref = reserving_type(&type_id, &name, inner);
+        return Ok(metadata_impl(ident, container.generics, type_ref));
+    }
+
     match container.data {
-        Data::Enum(variants) if variants.iter().all(|v| matches!(v.style, Style::Unit)) => {
+        // Only externally tagged unit variants are serialized as plain strings, the other
+        // representations need a union
+        Data::Enum(variants)
+            if matches!(container.attrs.tag(), TagType::External)
+                && variants.iter().all(|v| matches!(v.style, Style::Unit)) =>
+        {
             inner.append_all(quote! {
                 let mut rv = types::Enum {
                     name: #name_literal.into(),
@@ -46,7 +80,7 @@ pub fn type_metadata_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::
                 if variant.attrs.skip_deserializing() && variant.attrs.skip_serializing() {
                     continue;
                 }
-                let variant_name = Literal::string(&variant.ident.to_string());
+                let variant_name = Literal::string(&variant.ident.unraw().to_string());
                 let serialized_name = Literal::string(&variant.attrs.name().serialize_name());
                 inner.append_all(quote! {
                     rv.variants.push(types::EnumVariant {
@@ -57,39 +91,9 @@ pub fn type_metadata_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::
             }
 
             inner.append_all(quote! {
-                FieldType::Named(registry.register_enum(#type_id, rv))
+                registry.register_enum(#type_id, rv)
             });
         }
-        Data::Enum(variants) if variants.iter().all(|v| matches!(v.style, Style::Newtype)) => {
-            let repr = tag_to_representation(container.attrs.tag());
-            inner.append_all(quote! {
-                let mut rv = types::Union {
-                    name: #name_literal.into(),
-                    representation: #repr,
-                    variants: vec![]
-                };
-            });
-            for variant in variants {
-                if variant.attrs.skip_deserializing() && variant.attrs.skip_serializing() {
-                    continue;
-                }
-                let variant_name = Literal::string(&variant.ident.to_string());
-                let serialized_name = Literal::string(&variant.attrs.name().serialize_name());
-                let metadata_call = metadata_call(variant.fields.first().unwrap().ty);
-                inner.append_all(quote! {
-                    rv.variants.push(
-                        types::UnionVariant {
-                            name: Some(#variant_name.to_string()),
-                            ty: #metadata_call,
-                            serialized_name: #serialized_name.to_string()
-                        }
-                    );
-                })
-            }
-            inner.append_all(quote! {
-                FieldType::Named(registry.register_union(#type_id, rv))
-            })
-        }
         Data::Enum(variants) => {
             let repr = tag_to_representation(container.attrs.tag());
             inner.append_all(quote! {
@@ -103,60 +107,138 @@ pub fn type_metadata_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::
                 if variant.attrs.skip_deserializing() && variant.attrs.skip_serializing() {
                     continue;
                 }
-                let variant_name = Literal::string(&variant.ident.to_string());
+                let variant_name = Literal::string(&variant.ident.unraw().to_string());
                 let serialized_name = Literal::string(&variant.attrs.name().serialize_name());
-                let type_id =
-                    TypeIdCall::for_variant(&container.ident, &variant.ident, container.generics);
-                let inner_type_block =
-                    struct_block(&variant.ident.to_string(), &variant.fields, type_id);
+                let ty = match variant.style {
+                    Style::Unit => quote! { None },
+                    Style::Newtype => {
+                        let metadata_call = field_metadata_call(variant.fields.first().unwrap())?;
+                        quote! { Some(#metadata_call) }
+                    }
+                    Style::Struct | Style::Tuple => {
+                        let type_id = TypeIdCall::for_variant(
+                            &container.ident,
+                            &variant.ident,
+                            container.generics,
+                        );
+                        let inner_type_block = reserving_type(
+                            &type_id,
+                            &variant.ident.unraw().to_string(),
+                            struct_block(
+                                &variant.ident.unraw().to_string(),
+                                &variant.fields,
+                                false,
+                                &type_id,
+                            )?,
+                        );
+                        quote! { Some(FieldType::Named({#inner_type_block})) }
+                    }
+                };
                 inner.append_all(quote! {
                     rv.variants.push(
                         types::UnionVariant {
                             name: Some(#variant_name.to_string()),
-                            ty: FieldType::Named({#inner_type_block}),
+                            ty: #ty,
                             serialized_name: #serialized_name.to_string()
                         }
                     );
                 })
             }
             inner.append_all(quote! {
-                FieldType::Named(registry.register_union(#type_id, rv))
+                registry.register_union(#type_id, rv)
             })
         }
         Data::Struct(Style::Newtype, fields) => {
-            let metadata_call = metadata_call(fields.first().unwrap().ty);
+            let metadata_call = field_metadata_call(fields.first().unwrap())?;
             inner.append_all(quote! {
                 let nt = types::NewType {
                     name: #name_literal.to_string(),
                     inner: #metadata_call,
                 };
-                FieldType::Named(registry.register_newtype(#type_id, nt))
+                registry.register_newtype(#type_id, nt)
             });
         }
         Data::Struct(_, fields) => {
-            let struct_block_contents = struct_block(&ident.to_string(), &fields, type_id);
-            inner.append_all(quote! {
-                let type_ref = {
-                    #struct_block_contents
-                };
-                FieldType::Named(type_ref)
-            });
+            let container_default = !container.attrs.default().is_none();
+            inner.append_all(struct_block(&name, &fields, container_default, &type_id)?);
         }
     }
 
-    let (impl_generics, ty_generics, where_clause) = container.generics.split_for_impl();
-    Ok(quote! {
+    let type_ref = reserving_type(&type_id, &name, inner);
+
+    Ok(metadata_impl(ident, container.generics, type_ref))
+}
+
+fn metadata_impl(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    type_ref: TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
         #[automatically_derived]
         impl #impl_generics ::go_away::TypeMetadata for #ident #ty_generics #where_clause {
             fn metadata(registry: &mut ::go_away::TypeRegistry) -> ::go_away::types::FieldType {
                 use ::go_away::types::{self, FieldType};
-                #inner
+                FieldType::Named(#type_ref)
             }
         }
-    })
+    }
+}
+
+/// Gets the metadata call for the type that serde will actually use on the wire for a
+/// container, if it's not the container itself.
+///
+/// This covers `#[serde(transparent)]` and the `from`, `try_from` & `into` attributes.
+/// We're looking at things from the deserialization side, so `from` & `try_from`
+/// take precedence over `into`.
+fn wire_type_metadata_call(container: &Container<'_>) -> Result<Option<TokenStream>, syn::Error> {
+    if container.attrs.transparent() {
+        if let Data::Struct(_, fields) = &container.data {
+            return fields
+                .iter()
+                .find(|f| f.attrs.transparent())
+                .map(field_metadata_call)
+                .transpose();
+        }
+    }
+
+    Ok(container
+        .attrs
+        .type_from()
+        .or_else(|| container.attrs.type_try_from())
+        .or_else(|| container.attrs.type_into())
+        .map(metadata_call))
 }
 
-fn struct_block(name: &str, fields: &[Field], type_id: TypeIdCall<'_>) -> TokenStream {
+/// Wraps the block that registers a type so that it's only evaluated if the type
+/// hasn't already been registered.
+///
+/// The type is reserved before `register_block` runs, which stops recursive types
+/// from recursing forever.
+fn reserving_type(
+    type_id: &TypeIdCall<'_>,
+    name: &str,
+    register_block: TokenStream,
+) -> TokenStream {
+    let name_literal = Literal::string(name);
+    quote! {
+        match registry.lookup(&#type_id) {
+            Some(type_ref) => type_ref,
+            None => {
+                registry.reserve(#type_id, #name_literal);
+                #register_block
+            }
+        }
+    }
+}
+
+fn struct_block(
+    name: &str,
+    fields: &[Field],
+    container_default: bool,
+    type_id: &TypeIdCall<'_>,
+) -> Result<TokenStream, syn::Error> {
     use quote::TokenStreamExt;
 
     let mut rv = TokenStream::new();
@@ -174,13 +256,28 @@ fn struct_block(name: &str, fields: &[Field], type_id: TypeIdCall<'_>) -> TokenS
         }
         let field_name = name_of_member(&field.member);
         let serialized_name = Literal::string(&field.attrs.name().serialize_name());
-        let ty_def = metadata_call(field.ty);
+        let ty_def = field_metadata_call(field)?;
+        let go_tags = FieldAttrs::from_ast(field.original)?
+            .tags
+            .into_iter()
+            .map(|(key, value)| quote! { (#key.to_string(), #value.to_string()) });
+        let has_default = container_default || !field.attrs.default().is_none();
+        let skip_serializing_if = match field.attrs.skip_serializing_if() {
+            Some(expr) => {
+                let path = Literal::string(&path_to_string(&expr.path));
+                quote! { Some(#path.to_string()) }
+            }
+            None => quote! { None },
+        };
         rv.append_all(quote! {
             st.fields.push(
                 types::Field {
                     name: #field_name.into(),
                     serialized_name: #serialized_name.into(),
-                    ty: #ty_def
+                    ty: #ty_def,
+                    go_tags: vec![#(#go_tags),*],
+                    has_default: #has_default,
+                    skip_serializing_if: #skip_serializing_if
                 }
             );
         });
@@ -189,7 +286,7 @@ fn struct_block(name: &str, fields: &[Field], type_id: TypeIdCall<'_>) -> TokenS
         registry.register_struct(#type_id, st)
     });
 
-    rv
+    Ok(rv)
 }
 
 fn tag_to_representation(tag: &TagType) -> proc_macro2::TokenStream {
@@ -221,6 +318,27 @@ fn tag_to_representation(tag: &TagType) -> proc_macro2::TokenStream {
     }
 }
 
+/// Gets the metadata call for a field, taking into account any attributes that
+/// change the type it's serialized as.
+fn field_metadata_call(field: &Field) -> Result<TokenStream, syn::Error> {
+    let attrs = FieldAttrs::from_ast(field.original)?;
+    if let Some(as_type) = &attrs.as_type {
+        return Ok(metadata_call(as_type));
+    }
+
+    if field.attrs.serialize_with().is_some() || field.attrs.deserialize_with().is_some() {
+        // We've no idea what a custom serializer outputs, so rather than guessing based on
+        // the rust type we make the user tell us.
+        return Err(syn::Error::new_spanned(
+            field.original,
+            "go_away can't tell what type a field with a custom serde serializer is \
+             serialized as.  Add #[go_away(as = \"...\")] to specify the type",
+        ));
+    }
+
+    Ok(metadata_call(field.ty))
+}
+
 fn metadata_call(ty: &syn::Type) -> proc_macro2::TokenStream {
     match ty {
         syn::Type::Reference(r) => metadata_call(r.elem.as_ref()),
@@ -232,11 +350,25 @@ fn metadata_call(ty: &syn::Type) -> proc_macro2::TokenStream {
 fn name_of_member(member: &syn::Member) -> proc_macro2::Literal {
     use syn::Index;
     match member {
-        syn::Member::Named(ident) => Literal::string(&ident.to_string()),
+        syn::Member::Named(ident) => Literal::string(&ident.unraw().to_string()),
         syn::Member::Unnamed(Index { index, .. }) => Literal::string(&format!("_{}", index)),
     }
 }
 
+/// Converts a path like `Option::is_none` into a string, without any generics
+fn path_to_string(path: &syn::Path) -> String {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.unraw().to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    match path.leading_colon {
+        Some(_) => format!("::{segments}"),
+        None => segments,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
@@ -271,6 +403,190 @@ mod tests {
         }))
     }
 
+    #[test]
+    fn test_transparent_struct() {
+        assert_snapshot!(test_conversion(quote! {
+            #[serde(transparent)]
+            struct MyData {
+                data: String,
+                #[serde(skip)]
+                marker: PhantomData<u32>
+            }
+        }))
+    }
+
+    #[test]
+    fn test_from_into_struct() {
+        assert_snapshot!(test_conversion(quote! {
+            #[serde(from = "String", into = "String")]
+            struct MyData {
+                field_one: String,
+                field_two: String
+            }
+        }))
+    }
+
+    #[test]
+    fn test_field_with_as_override() {
+        assert_snapshot!(test_conversion(quote! {
+            struct MyData {
+                #[serde(with = "ts_seconds")]
+                #[go_away(as = "i64")]
+                created_at: DateTime<Utc>
+            }
+        }))
+    }
+
+    #[test]
+    fn test_field_with_go_tags() {
+        assert_snapshot!(test_conversion(quote! {
+            struct MyData {
+                #[go_away(tag(db = "user_id", validate = "uuid"))]
+                user_id: String
+            }
+        }))
+    }
+
+    #[test]
+    fn test_fields_with_skip_serializing_if() {
+        assert_snapshot!(test_conversion(quote! {
+            struct MyData {
+                #[serde(skip_serializing_if = "Option::is_none")]
+                nickname: Option<String>,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                tags: Vec<String>
+            }
+        }))
+    }
+
+    #[test]
+    fn test_fields_with_serde_defaults() {
+        assert_snapshot!(test_conversion(quote! {
+            struct MyData {
+                #[serde(default)]
+                retries: u32,
+                #[serde(default = "default_name")]
+                name: String,
+                required: bool
+            }
+        }))
+    }
+
+    #[test]
+    fn test_rename_all_fields() {
+        assert_snapshot!(test_conversion(quote! {
+            #[serde(rename_all_fields = "camelCase")]
+            enum Event {
+                UserCreated { user_id: String },
+                #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+                UserDeleted { user_id: String, #[serde(rename = "why")] deletion_reason: String },
+            }
+        }))
+    }
+
+    #[test]
+    fn test_serialize_with_requires_override() {
+        assert_snapshot!(test_error(quote! {
+            struct MyData {
+                #[serde(serialize_with = "to_timestamp")]
+                created_at: DateTime<Utc>
+            }
+        }), @r###"
+        go_away can't tell what type a field with a custom serde serializer is serialized as.  Add #[go_away(as = "...")] to specify the type
+        "###);
+    }
+
+    #[test]
+    fn test_invalid_go_tag_error() {
+        assert_snapshot!(test_error(quote! {
+            struct MyData {
+                #[go_away(tag(db))]
+                user_id: String
+            }
+        }), @r###"
+        expected go_away(tag(...)) to contain key = "value" pairs
+        "###);
+    }
+
+    #[test]
+    fn test_mixed_untagged_variants_error() {
+        assert_snapshot!(test_error(quote! {
+            enum MyData {
+                One(String),
+                #[serde(untagged)]
+                Two(i64)
+            }
+        }), @"go_away doesn't support #[serde(untagged)] variants mixed with tagged variants. Either put #[serde(untagged)] on the whole enum or remove it");
+    }
+
+    #[test]
+    fn test_other_variant_error() {
+        assert_snapshot!(test_error(quote! {
+            #[serde(tag = "type")]
+            enum MyData {
+                One { x: String },
+                #[serde(other)]
+                Unknown
+            }
+        }), @"go_away can't represent #[serde(other)] variants");
+    }
+
+    #[test]
+    fn test_remote_requires_rename() {
+        assert_snapshot!(test_error(quote! {
+            #[serde(remote = "Duration")]
+            struct DurationDef {
+                secs: u64,
+                nanos: u32
+            }
+        }), @r###"
+        go_away needs to know what to call the types generated from a #[serde(remote = "...")] definition. Add #[go_away(rename = "...")]
+        "###);
+    }
+
+    #[test]
+    fn test_remote_with_rename() {
+        assert_snapshot!(test_conversion(quote! {
+            #[serde(remote = "Duration")]
+            #[go_away(rename = "Duration")]
+            struct DurationDef {
+                secs: u64,
+                nanos: u32
+            }
+        }))
+    }
+
+    #[test]
+    fn test_rust_union_error() {
+        assert_snapshot!(test_error(quote! {
+            union MyData {
+                int: u32,
+                float: f32
+            }
+        }), @"go_away can't represent rust unions, use an enum instead");
+    }
+
+    #[test]
+    fn test_de_lifetime_error() {
+        assert_snapshot!(test_error(quote! {
+            struct MyData<'de> {
+                #[serde(borrow)]
+                data: &'de str
+            }
+        }), @"go_away doesn't support data borrowed via the 'de lifetime");
+    }
+
+    #[test]
+    fn test_other_borrowed_lifetimes() {
+        assert!(type_metadata_derive(&syn::parse_quote! {
+            struct MyData<'a> {
+                #[serde(borrow)]
+                data: &'a str
+            }
+        })
+        .is_ok());
+    }
+
     fn test_conversion(ts: proc_macro2::TokenStream) -> String {
         format_code(
             &type_metadata_derive(&syn::parse2(ts).unwrap())
@@ -279,6 +595,15 @@ mod tests {
         )
     }
 
+    fn test_error(ts: proc_macro2::TokenStream) -> String {
+        type_metadata_derive(&syn::parse2(ts).unwrap())
+            .unwrap_err()
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn format_code(text: &str) -> String {
         let sh = Shell::new().unwrap();
         xshell::cmd!(sh, "rustfmt").stdin(text).read().unwrap()
@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+
+use syn::{Meta, NestedMeta};
+
+/// Rewrites `#[serde(rename_all_fields = "...")]` on an enum into `#[serde(rename_all = "...")]`
+/// on each of its variants.
+///
+/// The version of serde_derive_internals we use predates `rename_all_fields` so we need to
+/// desugar it ourselves.  A variant that has its own `rename_all` takes precedence, which
+/// matches serde.
+pub fn desugar(input: &syn::DeriveInput) -> Result<Cow<'_, syn::DeriveInput>, syn::Error> {
+    let syn::Data::Enum(_) = &input.data else {
+        return Ok(Cow::Borrowed(input));
+    };
+
+    let mut rename_all_fields = Vec::new();
+    let mut attrs = Vec::with_capacity(input.attrs.len());
+    for attr in &input.attrs {
+        if !attr.path.is_ident("serde") {
+            attrs.push(attr.clone());
+            continue;
+        }
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            attrs.push(attr.clone());
+            continue;
+        };
+        let (ours, others): (Vec<_>, Vec<_>) = list
+            .nested
+            .into_iter()
+            .partition(|nested| is_key(nested, "rename_all_fields"));
+        if ours.is_empty() {
+            attrs.push(attr.clone());
+            continue;
+        }
+        rename_all_fields.extend(ours);
+        if !others.is_empty() {
+            attrs.push(syn::parse_quote!(#[serde(#(#others),*)]));
+        }
+    }
+
+    if rename_all_fields.is_empty() {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let rename_all = rename_all_fields
+        .into_iter()
+        .map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(mut name_value)) => {
+                name_value.path = rename_all_path(&name_value.path);
+                NestedMeta::Meta(Meta::NameValue(name_value))
+            }
+            NestedMeta::Meta(Meta::List(mut list)) => {
+                list.path = rename_all_path(&list.path);
+                NestedMeta::Meta(Meta::List(list))
+            }
+            other => other,
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = input.clone();
+    output.attrs = attrs;
+    if let syn::Data::Enum(data) = &mut output.data {
+        for variant in &mut data.variants {
+            if has_rename_all(&variant.attrs) {
+                continue;
+            }
+            variant
+                .attrs
+                .push(syn::parse_quote!(#[serde(#(#rename_all),*)]));
+        }
+    }
+
+    Ok(Cow::Owned(output))
+}
+
+fn rename_all_path(original: &syn::Path) -> syn::Path {
+    let span = original
+        .get_ident()
+        .map(|ident| ident.span())
+        .unwrap_or_else(proc_macro2::Span::call_site);
+
+    syn::Ident::new("rename_all", span).into()
+}
+
+fn has_rename_all(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list
+                .nested
+                .iter()
+                .any(|nested| is_key(nested, "rename_all")),
+            _ => false,
+        })
+}
+
+fn is_key(nested: &NestedMeta, key: &str) -> bool {
+    matches!(nested, NestedMeta::Meta(meta) if meta.path().is_ident(key))
+}
@@ -0,0 +1,145 @@
+use quote::ToTokens;
+use serde_derive_internals::ast::{Container, Data};
+use syn::{Meta, NestedMeta};
+
+use super::attrs::ContainerAttrs;
+
+/// Checks the raw input for things go_away can't represent.
+///
+/// This runs before serde parses the input, as serde would otherwise report some of
+/// these with less helpful errors.
+pub fn validate_input(input: &syn::DeriveInput) -> Result<(), syn::Error> {
+    let mut errors = Errors::default();
+
+    match &input.data {
+        syn::Data::Union(data) => {
+            errors.push(syn::Error::new_spanned(
+                data.union_token,
+                "go_away can't represent rust unions, use an enum instead",
+            ));
+        }
+        syn::Data::Enum(data) => {
+            for variant in &data.variants {
+                if has_serde_word(&variant.attrs, "untagged") {
+                    errors.push(syn::Error::new_spanned(
+                        variant,
+                        "go_away doesn't support #[serde(untagged)] variants mixed with tagged \
+                         variants. Either put #[serde(untagged)] on the whole enum or remove it",
+                    ));
+                }
+                for field in &variant.fields {
+                    check_de_lifetime(input, field, &mut errors);
+                }
+            }
+        }
+        syn::Data::Struct(data) => {
+            for field in &data.fields {
+                check_de_lifetime(input, field, &mut errors);
+            }
+        }
+    }
+
+    errors.finish()
+}
+
+/// Checks the serde parsed container for things go_away can't represent.
+pub fn validate_container(
+    container: &Container<'_>,
+    attrs: &ContainerAttrs,
+) -> Result<(), syn::Error> {
+    let mut errors = Errors::default();
+
+    if let Some(remote) = container.attrs.remote() {
+        if attrs.rename.is_none() {
+            errors.push(syn::Error::new_spanned(
+                remote,
+                "go_away needs to know what to call the types generated from a \
+                 #[serde(remote = \"...\")] definition. Add #[go_away(rename = \"...\")]",
+            ));
+        }
+    }
+
+    if let Data::Enum(variants) = &container.data {
+        for variant in variants {
+            if variant.attrs.other() {
+                errors.push(syn::Error::new_spanned(
+                    variant.original,
+                    "go_away can't represent #[serde(other)] variants",
+                ));
+            }
+        }
+    }
+
+    errors.finish()
+}
+
+/// Borrowing from the deserializer via a `'de` lifetime parameter is tied to serde's
+/// internals, so we can't describe it.
+///
+/// Other borrowed lifetimes (e.g. `&'a str`, with or without `#[serde(borrow)]`) are fine as
+/// borrowing doesn't change what goes over the wire, and so are higher ranked bounds like
+/// `for<'de> Deserialize<'de>` that don't refer to the container's own lifetime.
+fn check_de_lifetime(input: &syn::DeriveInput, field: &syn::Field, errors: &mut Errors) {
+    let declares_de = input
+        .generics
+        .lifetimes()
+        .any(|param| param.lifetime.ident == "de");
+    if !declares_de {
+        return;
+    }
+
+    let mentions_de = field
+        .ty
+        .to_token_stream()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .windows(2)
+        .any(|tokens| match tokens {
+            [proc_macro2::TokenTree::Punct(punct), proc_macro2::TokenTree::Ident(ident)] => {
+                punct.as_char() == '\'' && ident == "de"
+            }
+            _ => false,
+        });
+
+    if mentions_de {
+        errors.push(syn::Error::new_spanned(
+            field,
+            "go_away doesn't support data borrowed via the 'de lifetime",
+        ));
+    }
+}
+
+fn has_serde_word(attrs: &[syn::Attribute], word: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| {
+            match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| {
+                matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident(word))
+            }),
+            _ => false,
+        }
+        })
+}
+
+/// Accumulates errors so we can report all of them at once
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(existing) => existing.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn finish(self) -> Result<(), syn::Error> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
@@ -9,7 +9,11 @@ use go_away_derive_internals::type_metadata_derive;
 /// Derives TypeMetadata for a given struct.
 ///
 /// This allows go-away to generate go types for a given type.
-#[proc_macro_derive(TypeMetadata, attributes(serde))]
+///
+/// Fields can be annotated with `#[go_away(as = "SomeType")]` to describe them as
+/// a different type.  This is required for fields that use `#[serde(with = "...")]`
+/// or similar, as go-away has no way to tell what type those serialize as.
+#[proc_macro_derive(TypeMetadata, attributes(serde, go_away))]
 pub fn type_metadata_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 
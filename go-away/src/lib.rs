@@ -31,11 +31,22 @@
 //! Note that the output go code does not contain any package definitions or required imports.
 //! It's expected that any code that calls `go-away` will add this for itself.
 //!
+//! ## Features
+//!
+//! `TypeMetadata` is implemented for types from some common crates behind features of the same
+//! name, e.g. `uuid`, `chrono` or `rust_decimal`.  The `all` feature enables every one of them.
+//!
+//! `rust_decimal::Decimal` is described as a string, which is how rust_decimal serializes it by
+//! default.  If anything in your build enables `rust_decimal/serde-float` you must also enable
+//! go-away's `rust_decimal-serde-float` feature, or annotate decimal fields with
+//! `#[go_away(as = "f64")]` - go-away can't detect that feature on its own.
+//!
 
 #![warn(missing_docs)]
 
 mod alias;
 mod metadata;
+mod operation;
 mod output;
 mod registry;
 mod type_id;
@@ -44,7 +55,13 @@ pub mod types;
 
 pub use alias::TypeAlias;
 pub use metadata::TypeMetadata;
-pub use output::{GoType, KotlinType, SwiftType, TypeScriptType};
+pub use operation::OperationBuilder;
+pub use output::{
+    ConfigurableFormat, GoClient, GoClientOptions, GoOmitEmpty, GoOptions, GoType, GoUnionStyle,
+    JsonSchemaOptions, JsonSchemaType, KotlinType, OpenApiFormat, OpenApiOptions, SwiftType,
+    TypeScriptClient, TypeScriptClientOptions, TypeScriptEnumStyle, TypeScriptOptions,
+    TypeScriptStructStyle, TypeScriptType, TypeScriptWideIntegers, ZodSchema,
+};
 pub use registry::TypeRegistry;
 pub use type_id::TypeId;
 
@@ -66,28 +83,98 @@ where
     use std::fmt::Write;
 
     let mut output = String::new();
-    for id in registry.structs.iter().rev() {
-        let ty = registry.types.get(id).unwrap();
-        write!(&mut output, "{}", Format::from(ty)).unwrap();
-    }
-    for id in registry.unions.iter().rev() {
-        let ty = registry.types.get(id).unwrap();
-        write!(&mut output, "{}", Format::from(ty)).unwrap();
-    }
-    for id in registry.newtypes.iter().rev() {
-        let ty = registry.types.get(id).unwrap();
+    for ty in registry_types(registry) {
         write!(&mut output, "{}", Format::from(ty)).unwrap();
     }
-    for id in registry.enums.iter().rev() {
-        let ty = registry.types.get(id).unwrap();
-        write!(&mut output, "{}", Format::from(ty)).unwrap();
-    }
-    for id in registry.aliases.iter().rev() {
-        let ty = registry.types.get(id).unwrap();
-        write!(&mut output, "{}", Format::from(ty)).unwrap();
+
+    // Some formats separate each type with a leading blank line, which we don't want at the
+    // start of the file.
+    output.trim_start_matches('\n').to_string()
+}
+
+/// Generates code for all the types in the TypeRegistry, customised by some options
+///
+/// This works like `registry_to_output`, but only for formats that have options, e.g.
+/// `go_away::GoType` which takes a `go_away::GoOptions`.
+pub fn registry_to_output_with_options<'a, Format>(
+    registry: &'a TypeRegistry,
+    options: &Format::Options,
+) -> String
+where
+    Format: From<&'a Type> + ConfigurableFormat,
+{
+    let types = registry_types(registry)
+        .map(Format::from)
+        .collect::<Vec<_>>();
+
+    output_with_options(&types, options)
+}
+
+/// Generates an API client for all the operations in the TypeRegistry
+///
+/// You should provide a client format such as `go_away::TypeScriptClient` as a generic
+/// parameter to decide which language to output.  The client refers to the types by
+/// name, so the output of `registry_to_output` for the same language should be included
+/// alongside it.
+pub fn registry_to_client<'a, Format>(
+    registry: &'a TypeRegistry,
+    options: &Format::Options,
+) -> String
+where
+    Format: From<&'a types::Operation> + ConfigurableFormat,
+{
+    let operations = registry
+        .operations
+        .iter()
+        .map(Format::from)
+        .collect::<Vec<_>>();
+
+    output_with_options(&operations, options)
+}
+
+fn output_with_options<Format>(items: &[Format], options: &Format::Options) -> String
+where
+    Format: ConfigurableFormat,
+{
+    use std::fmt::Write;
+
+    let mut output = output::Prelude(items, options).to_string();
+    for item in items {
+        write!(&mut output, "{}", output::WithOptions(item, options)).unwrap();
     }
 
-    output
+    // Some formats separate each type with a leading blank line, which we don't want at the
+    // start of the file.
+    output.trim_start_matches('\n').to_string()
+}
+
+/// Generates an OpenAPI 3.1 document describing all the types in the TypeRegistry
+///
+/// Every type becomes a schema in `components`, and any operations registered with
+/// `TypeRegistry::operation` become `paths`.
+pub fn registry_to_openapi(registry: &TypeRegistry, options: &OpenApiOptions) -> String {
+    output::openapi::document(registry_types(registry), &registry.operations, options)
+}
+
+/// Generates a JSON schema (draft 2020-12) document describing all the types in the
+/// TypeRegistry
+///
+/// Every type is output in `$defs`, and refers to other types with `$ref`.
+pub fn registry_to_json_schema(registry: &TypeRegistry, options: &JsonSchemaOptions) -> String {
+    output::json_schema::document(registry_types(registry), options)
+}
+
+/// Iterates over all the types in the registry in the order we output them
+fn registry_types(registry: &TypeRegistry) -> impl Iterator<Item = &Type> {
+    registry
+        .structs
+        .iter()
+        .rev()
+        .chain(registry.unions.iter().rev())
+        .chain(registry.newtypes.iter().rev())
+        .chain(registry.enums.iter().rev())
+        .chain(registry.aliases.iter().rev())
+        .map(move |id| registry.types.get(id).unwrap())
 }
 
 impl<'a> From<&'a registry::Type> for output::go::GoType<'a> {
@@ -114,6 +201,42 @@ impl<'a> From<&'a registry::Type> for output::typescript::TypeScriptType<'a> {
     }
 }
 
+impl<'a> From<&'a types::Operation> for GoClient<'a> {
+    fn from(operation: &'a types::Operation) -> Self {
+        GoClient(operation)
+    }
+}
+
+impl<'a> From<&'a types::Operation> for TypeScriptClient<'a> {
+    fn from(operation: &'a types::Operation) -> Self {
+        TypeScriptClient(operation)
+    }
+}
+
+impl<'a> From<&'a registry::Type> for ZodSchema<'a> {
+    fn from(ty: &'a registry::Type) -> Self {
+        match ty {
+            registry::Type::Struct(inner) => ZodSchema::Struct(inner),
+            registry::Type::Enum(inner) => ZodSchema::Enum(inner),
+            registry::Type::Union(inner) => ZodSchema::Union(inner),
+            registry::Type::NewType(inner) => ZodSchema::NewType(inner),
+            registry::Type::Alias(inner) => ZodSchema::Alias(inner),
+        }
+    }
+}
+
+impl<'a> From<&'a registry::Type> for JsonSchemaType<'a> {
+    fn from(ty: &'a registry::Type) -> Self {
+        match ty {
+            registry::Type::Struct(inner) => JsonSchemaType::Struct(inner),
+            registry::Type::Enum(inner) => JsonSchemaType::Enum(inner),
+            registry::Type::Union(inner) => JsonSchemaType::Union(inner),
+            registry::Type::NewType(inner) => JsonSchemaType::NewType(inner),
+            registry::Type::Alias(inner) => JsonSchemaType::Alias(inner),
+        }
+    }
+}
+
 impl<'a> From<&'a registry::Type> for output::swift::SwiftType<'a> {
     fn from(ty: &'a registry::Type) -> Self {
         match ty {
@@ -1,4 +1,4 @@
-use crate::types::{FieldType, Primitive};
+use crate::types::{FieldType, IntKind, Primitive};
 
 use super::TypeRegistry;
 
@@ -30,6 +30,15 @@ where
     }
 }
 
+impl<T> TypeMetadata for Box<T>
+where
+    T: TypeMetadata + ?Sized,
+{
+    fn metadata(registry: &mut TypeRegistry) -> FieldType {
+        T::metadata(registry)
+    }
+}
+
 impl<K, V> TypeMetadata for std::collections::HashMap<K, V>
 where
     K: TypeMetadata,
@@ -75,22 +84,97 @@ impl<Tz: chrono::offset::TimeZone> TypeMetadata for chrono::DateTime<Tz> {
     }
 }
 
+/// Decimals are described as strings, which is how rust_decimal serializes them by default.
+///
+/// rust_decimal serializes them as floats if its `serde-float` feature is enabled, but we
+/// can only see our own features: enable go-away's `rust_decimal-serde-float` feature to
+/// have them described as floats.  If some other crate enables `rust_decimal/serde-float`
+/// without it, the output will say string while the JSON contains a float - either enable
+/// the feature or annotate each field with `#[go_away(as = "f64")]`.
+#[cfg(feature = "rust_decimal")]
+impl TypeMetadata for rust_decimal::Decimal {
+    fn metadata(_: &mut TypeRegistry) -> FieldType {
+        if cfg!(feature = "rust_decimal-serde-float") {
+            FieldType::Primitive(Primitive::Float)
+        } else {
+            FieldType::Primitive(Primitive::String)
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+impl TypeMetadata for url::Url {
+    fn metadata(_: &mut TypeRegistry) -> FieldType {
+        FieldType::Primitive(Primitive::String)
+    }
+}
+
+#[cfg(feature = "semver")]
+impl TypeMetadata for semver::Version {
+    fn metadata(_: &mut TypeRegistry) -> FieldType {
+        FieldType::Primitive(Primitive::String)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TypeMetadata for bytes::Bytes {
+    fn metadata(_: &mut TypeRegistry) -> FieldType {
+        // serde_json serializes byte buffers as an array of numbers
+        FieldType::List(Box::new(FieldType::Primitive(Primitive::Int(IntKind::U8))))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> TypeMetadata for indexmap::IndexMap<K, V, S>
+where
+    K: TypeMetadata,
+    V: TypeMetadata,
+{
+    fn metadata(registry: &mut TypeRegistry) -> FieldType {
+        let key = Box::new(K::metadata(registry));
+        let value = Box::new(V::metadata(registry));
+
+        FieldType::Map { key, value }
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> TypeMetadata for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: TypeMetadata,
+{
+    fn metadata(registry: &mut TypeRegistry) -> FieldType {
+        FieldType::List(Box::new(A::Item::metadata(registry)))
+    }
+}
+
+#[cfg(feature = "ordered-float")]
+impl<T> TypeMetadata for ordered_float::OrderedFloat<T>
+where
+    T: TypeMetadata,
+{
+    fn metadata(registry: &mut TypeRegistry) -> FieldType {
+        T::metadata(registry)
+    }
+}
+
 macro_rules! metadata_for_int {
-    () => {};
-    ($this:ty, $($tail:tt)*) => {
-        metadata_for_int!($this);
-        metadata_for_int!{$($tail)*}
-    };
-    ($int:ty) => {
-        impl TypeMetadata for $int {
-            fn metadata(_: &mut TypeRegistry) -> FieldType {
-                FieldType::Primitive(Primitive::Int)
+    ($($int:ty => $kind:ident),*) => {
+        $(
+            impl TypeMetadata for $int {
+                fn metadata(_: &mut TypeRegistry) -> FieldType {
+                    FieldType::Primitive(Primitive::Int(IntKind::$kind))
+                }
             }
-        }
+        )*
     };
 }
 
-metadata_for_int! {i8, i16, i32, i64, i128, u8, u16, u32, u64, u128}
+metadata_for_int! {
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128
+}
 
 macro_rules! metadata_for_float {
     () => {};
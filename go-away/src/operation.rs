@@ -0,0 +1,80 @@
+use super::{
+    types::{HttpMethod, Operation, PathParam},
+    TypeMetadata, TypeRegistry,
+};
+
+/// Builds up an `Operation` and registers it, along with all of the types it uses.
+///
+/// Created with `TypeRegistry::operation`.
+pub struct OperationBuilder<'a> {
+    registry: &'a mut TypeRegistry,
+    operation: Operation,
+}
+
+impl<'a> OperationBuilder<'a> {
+    pub(super) fn new(
+        registry: &'a mut TypeRegistry,
+        name: &str,
+        method: HttpMethod,
+        path: &str,
+    ) -> Self {
+        OperationBuilder {
+            registry,
+            operation: Operation {
+                name: name.to_string(),
+                method,
+                path: path.to_string(),
+                path_params: vec![],
+                query: None,
+                request: None,
+                response: None,
+                error: None,
+            },
+        }
+    }
+
+    /// Adds a parameter that appears in the path as `{name}`.
+    ///
+    /// These should be added in the order they appear in the path.
+    pub fn path_param<T: TypeMetadata>(mut self, name: &str) -> Self {
+        let ty = T::metadata(self.registry);
+        self.operation.path_params.push(PathParam {
+            name: name.to_string(),
+            ty,
+        });
+        self
+    }
+
+    /// Sets the type that's serialized into the query string
+    pub fn query<T: TypeMetadata>(mut self) -> Self {
+        self.operation.query = Some(T::metadata(self.registry));
+        self
+    }
+
+    /// Sets the type of the JSON request body
+    pub fn request<T: TypeMetadata>(mut self) -> Self {
+        self.operation.request = Some(T::metadata(self.registry));
+        self
+    }
+
+    /// Sets the type of the JSON response body for successful requests
+    pub fn response<T: TypeMetadata>(mut self) -> Self {
+        self.operation.response = Some(T::metadata(self.registry));
+        self
+    }
+
+    /// Sets the type of the JSON response body for requests that fail
+    pub fn error<T: TypeMetadata>(mut self) -> Self {
+        self.operation.error = Some(T::metadata(self.registry));
+        self
+    }
+
+    /// Registers the operation
+    ///
+    /// # Panics
+    ///
+    /// See `TypeRegistry::register_operation`
+    pub fn register(self) {
+        self.registry.register_operation(self.operation);
+    }
+}
@@ -0,0 +1,339 @@
+//! Go clients for the operations of an HTTP API
+
+use std::fmt::{self, Write};
+
+use indenter::indented;
+use indoc::writedoc;
+
+use super::{
+    go_quote,
+    gofmt::{self, CELL},
+    keywords::is_keyword,
+    tabify, ConfigurableFormat, GoOptions, HttpMethod, Operation, PathSegment,
+};
+
+/// The names of the variables, functions & packages used in each client method, which
+/// parameters can't share
+const LOCALS: &[&str] = &[
+    "c",
+    "ctx",
+    "path",
+    "query",
+    "body",
+    "response",
+    "err",
+    "doRequest",
+    "bytes",
+    "context",
+    "fmt",
+    "http",
+    "io",
+    "json",
+    "strings",
+    "url",
+];
+
+/// A method of a go API client, which calls an `Operation` with `net/http`
+///
+/// This shouldn't be instantiated directly but passed using turbofish operator
+/// to `registry_to_client`.  The client refers to types by name, so the output of
+/// `registry_to_output::<GoType>` needs to be in the same package.
+pub struct GoClient<'a>(pub &'a Operation);
+
+/// Options for customising the generated go API client.
+///
+/// Use with `registry_to_client::<GoClient>`.
+#[derive(Clone, Debug, Default)]
+pub struct GoClientOptions {
+    types: GoOptions,
+    package: Option<String>,
+}
+
+impl GoClientOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the options that the types were output with, so the client names things
+    /// in the same way.
+    pub fn with_type_options(mut self, types: GoOptions) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Starts the client with a `package` clause & the imports it needs, so it can be
+    /// written straight to its own file.
+    ///
+    /// By default there's no package clause or imports, like the types.
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+}
+
+impl ConfigurableFormat for GoClient<'_> {
+    type Options = GoClientOptions;
+
+    fn fmt_with_options(
+        &self,
+        options: &GoClientOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let mut code = String::new();
+        self.write_code(&options.types, &mut tabify::tabify(&mut code))?;
+
+        write!(f, "\n{}", gofmt::format(&code))
+    }
+
+    fn fmt_prelude(
+        operations: &[Self],
+        options: &GoClientOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if operations.is_empty() {
+            return Ok(());
+        }
+
+        let mut code = String::new();
+        let tabbed = &mut tabify::tabify(&mut code);
+        if let Some(package) = &options.package {
+            writedoc!(
+                tabbed,
+                r#"
+                    package {package}
+
+                    import (
+                        "bytes"
+                        "context"
+                        "encoding/json"
+                        "fmt"
+                        "io"
+                        "net/http"
+                        "net/url"
+                        "strings"
+                    )
+
+                "#
+            )?;
+        }
+        writedoc!(
+            tabbed,
+            r#"
+                // Client calls the operations of an HTTP API
+                type Client struct {{
+                    // BaseURL is the URL the API is served from, e.g. https://example.com/api
+                    BaseURL{CELL}string
+                    // HTTPClient sends the requests, http.DefaultClient is used if this is nil
+                    HTTPClient{CELL}*http.Client
+                }}
+
+                // RequestError is returned when the server responds with an error status.
+                //
+                // Body holds the response body, decoded as the operation's error type.
+                type RequestError[E any] struct {{
+                    StatusCode{CELL}int
+                    Body{CELL}E
+                }}
+
+                func (e *RequestError[E]) Error() string {{
+                    return fmt.Sprintf("request failed with status %d", e.StatusCode)
+                }}
+
+                // doRequest sends a request, encoding query & body if they're not nil, then decodes
+                // the response body into response, or into a *RequestError[E] for error statuses.
+                func doRequest[E any](ctx context.Context, c *Client, method string, path string, query any, body any, response any) error {{
+                    u := strings.TrimRight(c.BaseURL, "/") + path
+                    if query != nil {{
+                        values, err := queryValues(query)
+                        if err != nil {{
+                            return fmt.Errorf("encoding query: %w", err)
+                        }}
+                        if len(values) > 0 {{
+                            u += "?" + values.Encode()
+                        }}
+                    }}
+
+                    var reader io.Reader
+                    if body != nil {{
+                        data, err := json.Marshal(body)
+                        if err != nil {{
+                            return fmt.Errorf("encoding request body: %w", err)
+                        }}
+                        reader = bytes.NewReader(data)
+                    }}
+
+                    req, err := http.NewRequestWithContext(ctx, method, u, reader)
+                    if err != nil {{
+                        return err
+                    }}
+                    req.Header.Set("Accept", "application/json")
+                    if body != nil {{
+                        req.Header.Set("Content-Type", "application/json")
+                    }}
+
+                    httpClient := c.HTTPClient
+                    if httpClient == nil {{
+                        httpClient = http.DefaultClient
+                    }}
+                    resp, err := httpClient.Do(req)
+                    if err != nil {{
+                        return err
+                    }}
+                    defer resp.Body.Close()
+
+                    if resp.StatusCode < 200 || resp.StatusCode > 299 {{
+                        requestErr := &RequestError[E]{{StatusCode: resp.StatusCode}}
+                        if err := json.NewDecoder(resp.Body).Decode(&requestErr.Body); err != nil && err != io.EOF {{
+                            return fmt.Errorf("decoding error response with status %d: %w", resp.StatusCode, err)
+                        }}
+                        return requestErr
+                    }}
+                    if response == nil {{
+                        return nil
+                    }}
+                    if err := json.NewDecoder(resp.Body).Decode(response); err != nil {{
+                        return fmt.Errorf("decoding response: %w", err)
+                    }}
+                    return nil
+                }}
+
+                // queryValues converts a value to query parameters, named the same as its JSON fields
+                func queryValues(query any) (url.Values, error) {{
+                    data, err := json.Marshal(query)
+                    if err != nil {{
+                        return nil, err
+                    }}
+                    decoder := json.NewDecoder(bytes.NewReader(data))
+                    decoder.UseNumber()
+                    var fields map[string]any
+                    if err := decoder.Decode(&fields); err != nil {{
+                        return nil, err
+                    }}
+
+                    values := url.Values{{}}
+                    for key, value := range fields {{
+                        items, ok := value.([]any)
+                        if !ok {{
+                            items = []any{{value}}
+                        }}
+                        for _, item := range items {{
+                            if item != nil {{
+                                values.Add(key, fmt.Sprint(item))
+                            }}
+                        }}
+                    }}
+                    return values, nil
+                }}
+            "#
+        )?;
+
+        write!(f, "{}", gofmt::format(&code))
+    }
+}
+
+impl GoClient<'_> {
+    /// Writes the method for this operation, before it's been formatted by `gofmt::format`
+    fn write_code(&self, options: &GoOptions, f: &mut impl Write) -> fmt::Result {
+        let operation = self.0;
+        let name = options.identifier(&operation.name);
+
+        let mut params = vec!["ctx context.Context".to_string()];
+        for param in &operation.path_params {
+            params.push(format!(
+                "{} {}",
+                param_name(&param.name, options),
+                param.ty.go_type()
+            ));
+        }
+        if let Some(query) = &operation.query {
+            params.push(format!("query {}", query.go_type()));
+        }
+        if let Some(request) = &operation.request {
+            params.push(format!("body {}", request.go_type()));
+        }
+        let error = operation
+            .error
+            .as_ref()
+            .map(|ty| ty.go_type())
+            .unwrap_or_else(|| "json.RawMessage".to_string());
+
+        writeln!(
+            f,
+            "// {name} calls `{} {}`.",
+            operation.method.as_str(),
+            operation.path
+        )?;
+        writeln!(f, "//")?;
+        writeln!(
+            f,
+            "// If the server responds with an error status this returns a *RequestError[{error}]."
+        )?;
+        let returns = match &operation.response {
+            Some(response) => format!("({}, error)", response.go_type()),
+            None => "error".to_string(),
+        };
+        writeln!(
+            f,
+            "func (c *Client) {name}({}) {returns} {{",
+            params.join(", ")
+        )?;
+
+        let body = &mut indented(f);
+        writeln!(body, "path := {}", path_expr(operation, options))?;
+        let args = format!(
+            "ctx, c, {}, path, {}, {}",
+            method_const(operation.method),
+            operation.query.as_ref().map(|_| "query").unwrap_or("nil"),
+            operation.request.as_ref().map(|_| "body").unwrap_or("nil"),
+        );
+        match &operation.response {
+            Some(response) => {
+                writeln!(body, "var response {}", response.go_type())?;
+                writeln!(
+                    body,
+                    "err := doRequest[{error}]({args}, &response)\nreturn response, err"
+                )?;
+            }
+            None => writeln!(body, "return doRequest[{error}]({args}, nil)")?,
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// A go expression for the path of `operation`, with its parameters substituted in
+fn path_expr(operation: &Operation, options: &GoOptions) -> String {
+    let parts = operation
+        .path_segments()
+        .into_iter()
+        .map(|segment| match segment {
+            PathSegment::Literal(literal) => go_quote(literal),
+            PathSegment::Param(name) => {
+                format!("url.PathEscape(fmt.Sprint({}))", param_name(name, options))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match parts.is_empty() {
+        true => "\"\"".to_string(),
+        false => parts.join(" + "),
+    }
+}
+
+fn param_name(name: &str, options: &GoOptions) -> String {
+    let name = options.unexported_identifier(name);
+    match is_keyword(&name) || LOCALS.contains(&name.as_str()) {
+        true => format!("{name}_"),
+        false => name,
+    }
+}
+
+fn method_const(method: HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "http.MethodGet",
+        HttpMethod::Post => "http.MethodPost",
+        HttpMethod::Put => "http.MethodPut",
+        HttpMethod::Patch => "http.MethodPatch",
+        HttpMethod::Delete => "http.MethodDelete",
+    }
+}
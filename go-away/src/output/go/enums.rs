@@ -0,0 +1,99 @@
+use std::fmt::{self, Write};
+
+use indoc::writedoc;
+
+use super::{go_quote, indented, validate::EnumValidate, Enum, EnumVariant, GoOptions, CELL};
+
+/// Writes out a go string enum along with its constants & helper methods
+pub struct GoEnum<'a>(pub &'a Enum, pub &'a GoOptions);
+
+impl GoEnum<'_> {
+    /// The name of the go constant for `variant`
+    fn constant_name(&self, variant: &EnumVariant) -> String {
+        format!("{}{}", self.0.name, self.1.identifier(&variant.name))
+    }
+}
+
+impl fmt::Display for GoEnum<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let GoEnum(details, options) = self;
+        let constants = details
+            .variants
+            .iter()
+            .map(|variant| self.constant_name(variant))
+            .collect::<Vec<_>>();
+
+        writeln!(f, "type {} string\n", details.name)?;
+        writeln!(f, "const (")?;
+        for (constant, variant) in constants.iter().zip(&details.variants) {
+            writeln!(
+                indented(f),
+                "{}{CELL}{}{CELL}= {}",
+                constant,
+                details.name,
+                go_quote(&variant.serialized_name)
+            )?;
+        }
+        writeln!(f, ")\n")?;
+
+        writeln!(f, "func All{name}Values() []{name} {{", name = details.name)?;
+        writeln!(indented(f), "return []{}{{", details.name)?;
+        for constant in &constants {
+            writeln!(indented(f), "\t{constant},")?;
+        }
+        writeln!(indented(f), "}}")?;
+        writeln!(f, "}}\n")?;
+
+        writeln!(f, "func (e {}) IsValid() bool {{", details.name)?;
+        writeln!(indented(f), "switch e {{")?;
+        if !constants.is_empty() {
+            writeln!(indented(f), "case {}:", constants.join(", "))?;
+            writeln!(indented(f), "\treturn true")?;
+        }
+        writeln!(indented(f), "}}")?;
+        writeln!(indented(f), "return false")?;
+        writeln!(f, "}}\n")?;
+
+        writedoc!(
+            f,
+            r#"
+                func (e {name}) String() string {{
+                    return string(e)
+                }}
+
+            "#,
+            name = details.name
+        )?;
+
+        writeln!(
+            f,
+            "func (e *{}) UnmarshalJSON(data []byte) error {{",
+            details.name
+        )?;
+        writedoc!(
+            indented(f),
+            r#"
+                var value string
+                if err := json.Unmarshal(data, &value); err != nil {{
+                    return err
+                }}
+            "#
+        )?;
+        if !options.preserve_unknown_enum_values {
+            writedoc!(
+                indented(f),
+                r#"
+                    if !{name}(value).IsValid() {{
+                        return fmt.Errorf("unknown {name} value: %q", value)
+                    }}
+                "#,
+                name = details.name
+            )?;
+        }
+        writeln!(indented(f), "*e = {}(value)", details.name)?;
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}\n")?;
+
+        write!(f, "{}", EnumValidate(details, options))
+    }
+}
@@ -0,0 +1,196 @@
+//! Formatting to match the output of gofmt.
+//!
+//! We generate code that is already mostly laid out the way gofmt would, but alignment
+//! depends on the neighbouring lines.  Like gofmt's own printer, we mark the end of any
+//! cells that should be aligned with `CELL` and then line the cells up afterwards.
+
+/// Marks the end of a cell that should be aligned with the same cell on neighbouring lines.
+///
+/// e.g. struct fields are written as `Name{CELL}Type{CELL}Tags`
+pub const CELL: char = '\x0b';
+
+/// Formats go code the way gofmt would.
+///
+/// This aligns any cells, strips trailing whitespace, collapses runs of blank lines
+/// and makes sure there's a blank line between top level declarations, keeping any
+/// doc comments attached to the declaration that follows them.
+pub fn format(code: &str) -> String {
+    let lines = align(code);
+
+    let mut output = String::with_capacity(code.len());
+    let mut previous_blank = true;
+    let mut previous_comment = false;
+    for line in &lines {
+        let line = line.trim_end();
+        let blank = line.is_empty();
+        if blank && previous_blank {
+            continue;
+        }
+        // Doc comments stay attached to the declaration they're documenting
+        let comment = line.starts_with("//");
+        if !previous_blank && !previous_comment && (starts_declaration(line) || comment) {
+            output.push('\n');
+        }
+        output.push_str(line);
+        output.push('\n');
+        previous_blank = blank;
+        previous_comment = comment;
+    }
+
+    let trimmed_len = output.trim_end().len();
+    output.truncate(trimmed_len);
+    output.push('\n');
+    output
+}
+
+fn starts_declaration(line: &str) -> bool {
+    ["type ", "func ", "const ", "var "]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+/// A line split up into its indentation, cells & any trailing text
+struct Line<'a> {
+    indent: &'a str,
+    cells: Vec<&'a str>,
+    trailing: &'a str,
+}
+
+impl<'a> Line<'a> {
+    fn parse(line: &'a str) -> Self {
+        let content = line.trim_start_matches('\t');
+        let indent = &line[..line.len() - content.len()];
+        let mut cells = content.split(CELL).collect::<Vec<_>>();
+        let trailing = cells.pop().unwrap_or_default();
+        Line {
+            indent,
+            cells,
+            trailing,
+        }
+    }
+}
+
+/// Pads the cells of each line so they line up with the cells of their neighbours.
+///
+/// This follows the rules of go's tabwriter: a column is made up of the cells of
+/// consecutive lines that all have a cell in that column, and each cell is padded to
+/// the width of the widest cell in its column plus one space.  The last bit of text on
+/// a line is never part of a column.
+fn align(code: &str) -> Vec<String> {
+    let lines = code.lines().map(Line::parse).collect::<Vec<_>>();
+    let mut widths = lines
+        .iter()
+        .map(|line| vec![0; line.cells.len()])
+        .collect::<Vec<_>>();
+
+    let max_cells = lines.iter().map(|line| line.cells.len()).max().unwrap_or(0);
+    for column in 0..max_cells {
+        let mut start = 0;
+        while start < lines.len() {
+            let in_column =
+                |line: &Line<'_>| line.cells.len() > column && line.indent == lines[start].indent;
+            if !in_column(&lines[start]) {
+                start += 1;
+                continue;
+            }
+            let end = (start..lines.len())
+                .find(|&index| !in_column(&lines[index]))
+                .unwrap_or(lines.len());
+            let width = lines[start..end]
+                .iter()
+                .map(|line| line.cells[column].chars().count())
+                .max()
+                .unwrap_or(0);
+            for line_widths in &mut widths[start..end] {
+                line_widths[column] = width + 1;
+            }
+            start = end;
+        }
+    }
+
+    lines
+        .iter()
+        .zip(widths)
+        .map(|(line, widths)| {
+            let mut output = line.indent.to_string();
+            for (cell, width) in line.cells.iter().zip(widths) {
+                output.push_str(cell);
+                output.extend(std::iter::repeat(' ').take(width - cell.chars().count()));
+            }
+            output.push_str(line.trailing);
+            output
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment() {
+        let code = "type Config struct {\n\
+            \tHost\x0bstring\x0b`json:\"host\"`\n\
+            \tPortNumber\x0bint\x0b`json:\"port_number\"`\n\
+            \n\
+            \tID\x0bstring\x0b`json:\"id\"`\n\
+            }\n";
+
+        insta::assert_snapshot!(format(code), @r###"
+        type Config struct {
+        	Host       string `json:"host"`
+        	PortNumber int    `json:"port_number"`
+
+        	ID string `json:"id"`
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_columns_end_at_lines_without_cells() {
+        let code = "type Union struct {\n\
+            \tA\x0bbool\n\
+            \tLongerName\x0b*string\n\
+            \tEmbedded\n\
+            \tB\x0bint\n\
+            }\n";
+
+        insta::assert_snapshot!(format(code), @r###"
+        type Union struct {
+        	A          bool
+        	LongerName *string
+        	Embedded
+        	B int
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_declarations_are_separated() {
+        let code = "type A string\nfunc (a A) String() string {\n\treturn string(a)\n}\n\n\n\ntype B string   \n\n";
+
+        insta::assert_snapshot!(format(code), @r###"
+        type A string
+
+        func (a A) String() string {
+        	return string(a)
+        }
+
+        type B string
+        "###);
+    }
+
+    #[test]
+    fn test_doc_comments_stay_attached() {
+        let code = "type A string\n// B is documented\n//\n// Over several lines\ntype B string\n";
+
+        insta::assert_snapshot!(format(code), @r###"
+        type A string
+
+        // B is documented
+        //
+        // Over several lines
+        type B string
+        "###);
+    }
+}
@@ -0,0 +1,74 @@
+/// Go's reserved keywords
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// Methods that we generate on types, which fields can't share a name with
+const GENERATED_METHODS: &[&str] = &["MarshalJSON", "UnmarshalJSON", "Validate"];
+
+/// Methods that we generate on unions, which variants can't share a name with.
+///
+/// Unions also get an `As<Variant>` accessor for each variant with data, see
+/// `escape_variant_names`.
+const UNION_METHODS: &[&str] = &["Kind", "Visit"];
+
+/// Escapes a field name that would clash with a keyword or one of our generated methods
+/// by adding an underscore suffix.
+pub fn escape_field_name(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) || GENERATED_METHODS.contains(&name.as_str()) {
+        return format!("{name}_");
+    }
+    name
+}
+
+/// Escapes the names of a unions variants, which become fields of the union struct.
+///
+/// `variants` holds the go name of each variant along with whether it has data.  As
+/// well as the clashes `escape_field_name` handles, a variant can't share a name with
+/// the `Kind` & `Visit` methods or another variants `As<Variant>` accessor.
+pub fn escape_variant_names(variants: Vec<(String, bool)>) -> Vec<String> {
+    let accessors = variants
+        .iter()
+        .filter(|(_, has_data)| *has_data)
+        .map(|(name, _)| format!("As{name}"))
+        .collect::<Vec<_>>();
+
+    variants
+        .into_iter()
+        .map(|(name, _)| {
+            if UNION_METHODS.contains(&name.as_str()) || accessors.contains(&name) {
+                return format!("{name}_");
+            }
+            escape_field_name(name)
+        })
+        .collect()
+}
+
+/// Whether `name` is one of go's reserved keywords
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
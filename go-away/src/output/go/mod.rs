@@ -3,10 +3,25 @@ use std::{fmt, fmt::Write};
 use indenter::indented;
 use indoc::writedoc;
 
+mod client;
+mod enums;
+mod gofmt;
+mod keywords;
+mod options;
+mod sealed;
+mod unions;
 mod validate;
 
-use super::tabify;
-use validate::UnionValidate;
+use super::{tabify, ConfigurableFormat};
+use enums::GoEnum;
+use gofmt::CELL;
+use keywords::{escape_field_name, escape_variant_names};
+use sealed::SealedUnion;
+use unions::UnionHelpers;
+use validate::{NewTypeValidate, StructValidate, UnionValidate};
+
+pub use client::{GoClient, GoClientOptions};
+pub use options::{GoOmitEmpty, GoOptions, GoUnionStyle};
 
 pub use crate::types::*;
 
@@ -29,50 +44,67 @@ pub enum GoType<'a> {
 
 impl fmt::Display for GoType<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let f = &mut tabify::tabify(f);
+        self.fmt_with_options(&GoOptions::default(), f)
+    }
+}
+
+impl ConfigurableFormat for GoType<'_> {
+    type Options = GoOptions;
+
+    fn fmt_with_options(&self, options: &GoOptions, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut code = String::new();
+        self.write_code(options, &mut tabify::tabify(&mut code))?;
+
+        // Each type starts with a blank line to separate it from the previous one,
+        // registry_to_output strips this from the start of the file.
+        write!(f, "\n{}", gofmt::format(&code))
+    }
+}
+
+impl GoType<'_> {
+    /// Writes the code for this type, before it's been formatted by `gofmt::format`
+    fn write_code(&self, options: &GoOptions, f: &mut impl Write) -> fmt::Result {
         match self {
             GoType::Struct(details) => {
                 writeln!(f, "type {} struct {{", details.name)?;
                 for field in &details.fields {
-                    writeln!(indented(f), "{}", GoField(field))?;
+                    writeln!(indented(f), "{}", GoField(field, options))?;
                 }
-                writeln!(f, "}}")?;
+                writeln!(f, "}}\n")?;
+                write!(f, "{}", StructValidate(details, options))?;
             }
             GoType::NewType(details) => {
-                writeln!(f, "type {} {}", details.name, details.inner.go_type())?;
+                writeln!(f, "type {} {}\n", details.name, details.inner.go_type())?;
+                write!(
+                    f,
+                    "{}",
+                    NewTypeValidate {
+                        name: &details.name,
+                        inner: &details.inner
+                    }
+                )?;
             }
             GoType::Alias(details) => {
-                writeln!(f, "type {} {}", details.name, details.inner.go_type())?;
+                writeln!(f, "type {} {}\n", details.name, details.inner.go_type())?;
+                write!(
+                    f,
+                    "{}",
+                    NewTypeValidate {
+                        name: &details.name,
+                        inner: &details.inner
+                    }
+                )?;
             }
             GoType::Enum(details) => {
-                writeln!(f, "type {} string\n", details.name)?;
-                writeln!(f, "const (")?;
-                for variant in &details.variants {
-                    writeln!(
-                        indented(f),
-                        "{}{} {} = \"{}\"",
-                        details.name,
-                        variant.name,
-                        details.name,
-                        variant.serialized_name
-                    )?;
-                }
-                writeln!(f, ")")?;
+                write!(f, "{}", GoEnum(details, options))?;
             }
             GoType::Union(details) => {
-                writeln!(f, "type {} struct {{", details.name)?;
-                for variant in &details.variants {
-                    writeln!(
-                        indented(f),
-                        "{} *{}",
-                        variant.go_name(),
-                        variant.ty.go_type()
-                    )?;
+                let details = &go_union(details, options);
+                match options.union_style {
+                    GoUnionStyle::Struct => write!(f, "{}", StructUnion(details))?,
+                    GoUnionStyle::SealedInterface => write!(f, "{}", SealedUnion(details))?,
                 }
-                writeln!(f, "}}\n")?;
-                write!(f, "{}", UnionMarshal(details))?;
-                write!(f, "{}", UnionUnmarshal(details))?;
-                write!(f, "{}", UnionValidate(details))?;
+                write!(f, "{}", UnionHelpers(details, options.union_style))?;
             }
         }
 
@@ -80,22 +112,109 @@ impl fmt::Display for GoType<'_> {
     }
 }
 
-pub struct GoField<'a>(&'a Field);
+pub struct GoField<'a>(&'a Field, &'a GoOptions);
 
 impl fmt::Display for GoField<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let details = self.0;
+        let GoField(details, options) = self;
         write!(
             f,
-            r#"{} {} `json:"{}"`"#,
-            to_pascal_case(&details.name),
-            details.ty.go_type(),
-            details.serialized_name
+            "{}{CELL}{}{CELL}`{}`",
+            go_field_name(details, options),
+            details.ty.go_field_type(),
+            self.tags()
+                .into_iter()
+                .map(|(key, value)| format!("{key}:{}", go_quote(&value)))
+                .collect::<Vec<_>>()
+                .join(" ")
         )
     }
 }
 
+impl GoField<'_> {
+    /// The struct tags for this field as `(key, value)` pairs
+    fn tags(&self) -> Vec<(String, String)> {
+        let GoField(details, options) = self;
+        let is_optional = matches!(details.ty, FieldType::Optional(_));
+
+        let omit_empty = match options.omit_empty {
+            GoOmitEmpty::Never => false,
+            GoOmitEmpty::Optional => is_optional,
+            GoOmitEmpty::Always => true,
+        };
+        let name = match omit_empty {
+            true => format!("{},omitempty", details.serialized_name),
+            false => details.serialized_name.clone(),
+        };
+
+        let mut tags = vec![("json".to_string(), name.clone())];
+        tags.extend(
+            options
+                .tag_keys
+                .iter()
+                .map(|key| (key.clone(), name.clone())),
+        );
+        if options.validate_required && !is_optional {
+            tags.push(("validate".to_string(), "required".to_string()));
+        }
+
+        for (key, value) in &details.go_tags {
+            match tags.iter_mut().find(|(existing, _)| existing == key) {
+                // Validation rules are combined with `required`, anything else is replaced
+                Some((_, existing)) if key == "validate" => {
+                    existing.push(',');
+                    existing.push_str(value);
+                }
+                Some((_, existing)) => *existing = value.clone(),
+                None => tags.push((key.clone(), value.clone())),
+            }
+        }
+
+        tags
+    }
+}
+
+/// Quotes `value` as a go interpreted string literal.
+///
+/// This is also the format of struct tag values.  Struct tags are written inside a raw
+/// string, so backticks are escaped as well.
+pub(super) fn go_quote(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '`' => output.push_str("\\x60"),
+            c if c.is_ascii_control() => write!(output, "\\x{:02x}", c as u32).unwrap(),
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// The name of the go struct field for `field`
+fn go_field_name(field: &Field, options: &GoOptions) -> String {
+    escape_field_name(options.identifier(&field.name))
+}
+
 impl FieldType {
+    /// The go type to use for a struct field of this type.
+    ///
+    /// Go structs can't contain themselves by value so any references to recursive
+    /// types need to go behind a pointer.
+    fn go_field_type(&self) -> String {
+        match self {
+            FieldType::Named(type_ref) if type_ref.is_recursive() => format!("*{}", self.go_type()),
+            _ => self.go_type(),
+        }
+    }
+
     fn go_type(&self) -> String {
         match self {
             FieldType::Named(type_ref) => type_ref.name().to_string(),
@@ -104,13 +223,34 @@ impl FieldType {
             FieldType::Map { key, value } => format!("map[{}]{}", key.go_type(), value.go_type()),
             FieldType::Primitive(Primitive::String) => "string".to_string(),
             FieldType::Primitive(Primitive::Float) => "float64".to_string(),
-            FieldType::Primitive(Primitive::Int) => "int".to_string(),
+            FieldType::Primitive(Primitive::Int(_)) => "int".to_string(),
             FieldType::Primitive(Primitive::Bool) => "bool".to_string(),
             FieldType::Primitive(Primitive::Time) => "time.Time".to_string(),
         }
     }
 }
 
+/// Writes a union as a struct with a field for each variant, along with its
+/// marshalling & validation code.
+struct StructUnion<'a>(&'a Union);
+
+impl fmt::Display for StructUnion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        writeln!(f, "type {} struct {{", details.name)?;
+        for variant in &details.variants {
+            match &variant.ty {
+                Some(ty) => writeln!(indented(f), "{}{CELL}*{}", variant.go_name(), ty.go_type())?,
+                None => writeln!(indented(f), "{}{CELL}bool", variant.go_name())?,
+            }
+        }
+        writeln!(f, "}}\n")?;
+        write!(f, "{}", UnionMarshal(details))?;
+        write!(f, "{}", UnionUnmarshal(details))?;
+        write!(f, "{}", UnionValidate(details))
+    }
+}
+
 struct UnionMarshal<'a>(&'a Union);
 struct UnionUnmarshal<'a>(&'a Union);
 
@@ -127,9 +267,10 @@ impl fmt::Display for UnionMarshal<'_> {
             "#,
             details.name
         )?;
+        let body = &mut indented(f);
         for variant in details.variants.iter() {
-            let f = &mut indented(f);
-            writeln!(f, "if self.{} != nil {{", variant.go_name())?;
+            let f = &mut *body;
+            writeln!(f, "if {} {{", variant.go_is_set("self"))?;
             match &details.representation {
                 UnionRepresentation::AdjacentlyTagged { tag, content } => {
                     write!(
@@ -149,29 +290,74 @@ impl fmt::Display for UnionMarshal<'_> {
                         InternallyTaggedMarshaller { tag, variant }
                     )?;
                 }
-                _ => todo!("Implement the other tagging enum representations"),
+                UnionRepresentation::ExternallyTagged => {
+                    write!(indented(f), "{}", ExternallyTaggedMarshaller { variant })?;
+                }
+                UnionRepresentation::Untagged => {
+                    write!(indented(f), "{}", UntaggedMarshaller { variant })?;
+                }
             }
-            write!(f, "}}")?;
-            write!(f, " else ")?;
+            write!(f, "}} else ")?;
         }
-        writeln!(indented(f), "{{")?;
-        writeln!(
-            indented(f),
-            "\treturn nil, fmt.Errorf(\"No variant was present\")"
-        )?;
-        writeln!(indented(f), "}}")?;
+        writeln!(body, "{{")?;
+        writeln!(body, "\treturn nil, fmt.Errorf(\"No variant was present\")")?;
+        writeln!(body, "}}")?;
         writeln!(f, "}}")?;
 
         Ok(())
     }
 }
 
+/// Makes an exported go identifier out of a serialized name, e.g. `var-one` becomes `VarOne`
+fn exported_name(serialized_name: &str) -> String {
+    let mut name = String::with_capacity(serialized_name.len());
+    for word in serialized_name.split(|c: char| !c.is_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.extend(chars);
+        }
+    }
+    if !name.starts_with(char::is_alphabetic) {
+        name.insert_str(0, "Variant");
+    }
+    name
+}
+
 impl UnionVariant {
     fn go_name(&self) -> String {
         match (&self.name, &self.ty) {
             (Some(name), _) => name.clone(),
-            (_, FieldType::Named(type_ref)) => type_ref.name().to_string(),
-            _ => todo!("Variant must be named or named type for now (fix this later)"),
+            (_, Some(FieldType::Named(type_ref))) => type_ref.name().to_string(),
+            _ => exported_name(&self.serialized_name),
+        }
+    }
+
+    /// The go type of the data inside this variant.
+    ///
+    /// Unit variants have no data so callers need to handle them before calling this.
+    fn go_type(&self) -> String {
+        self.ty
+            .as_ref()
+            .expect("go_type to only be called on variants with data")
+            .go_type()
+    }
+
+    /// A go expression that checks whether this variant is set on `receiver`
+    ///
+    /// Unit variants are represented as bools, everything else is a pointer.
+    fn go_is_set(&self, receiver: &str) -> String {
+        match self.ty {
+            Some(_) => format!("{}.{} != nil", receiver, self.go_name()),
+            None => format!("{}.{}", receiver, self.go_name()),
+        }
+    }
+
+    /// A go statement that clears this variant on `receiver`
+    fn go_clear(&self, receiver: &str) -> String {
+        match self.ty {
+            Some(_) => format!("{}.{} = nil", receiver, self.go_name()),
+            None => format!("{}.{} = false", receiver, self.go_name()),
         }
     }
 }
@@ -184,6 +370,18 @@ struct AdjacentlyTaggedMarshaller<'a> {
 
 impl fmt::Display for AdjacentlyTaggedMarshaller<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variant.ty.is_none() {
+            return writedoc! {
+                f,
+                r#"
+                    output := make(map[string]interface{{}})
+                    output["{tag}"] = "{serialized_name}"
+                    return json.Marshal(output)
+                "#,
+                tag = self.tag,
+                serialized_name = self.variant.serialized_name,
+            };
+        }
         writedoc! {
             f,
             r#"
@@ -207,28 +405,77 @@ struct InternallyTaggedMarshaller<'a> {
 
 impl fmt::Display for InternallyTaggedMarshaller<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variant.ty.is_none() {
+            return writedoc! {
+                f,
+                r#"
+                    return json.Marshal(struct {{
+                        Tag string `json:"{tag}"`
+                    }}{{
+                        Tag: "{serialized_name}",
+                    }})
+                "#,
+                tag = self.tag,
+                serialized_name = self.variant.serialized_name,
+            };
+        }
         writedoc! {
             f,
             r#"
-                return json.Marshal(struct{{
+                return json.Marshal(struct {{
                     Tag string `json:"{tag}"`
                     {variant_type}
                 }}{{
-                    Tag: "{serialized_name}",
-                    {variant_type}: *self.{variant_go_name},
+                    Tag:{CELL}"{serialized_name}",
+                    {variant_type}:{CELL}*self.{variant_go_name},
                 }})
             "#,
             tag = self.tag,
             serialized_name = self.variant.serialized_name,
             variant_go_name = self.variant.go_name(),
-            variant_type = self.variant.ty.go_type()
+            variant_type = self.variant.go_type()
+        }
+    }
+}
+
+struct ExternallyTaggedMarshaller<'a> {
+    variant: &'a UnionVariant,
+}
+
+impl fmt::Display for ExternallyTaggedMarshaller<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variant.ty.is_none() {
+            return writeln!(
+                f,
+                "return json.Marshal(\"{}\")",
+                self.variant.serialized_name
+            );
+        }
+        writedoc! {
+            f,
+            r#"
+                return json.Marshal(map[string]interface{{}}{{
+                    "{serialized_name}": self.{variant_go_name},
+                }})
+            "#,
+            serialized_name = self.variant.serialized_name,
+            variant_go_name = self.variant.go_name()
         }
     }
 }
 
-// TODO: Adjacent needs {"t": "tag", "d": data}
-// Internal needs {"type": "tag", ..rest_fields}
-// External needs {"tag": {...}}
+struct UntaggedMarshaller<'a> {
+    variant: &'a UnionVariant,
+}
+
+impl fmt::Display for UntaggedMarshaller<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.variant.ty {
+            Some(_) => writeln!(f, "return json.Marshal(self.{})", self.variant.go_name()),
+            None => writeln!(f, "return json.Marshal(nil)"),
+        }
+    }
+}
 
 impl fmt::Display for UnionUnmarshal<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -250,6 +497,18 @@ impl fmt::Display for UnionUnmarshal<'_> {
                 writeln!(f, "\treturn err")?;
                 writeln!(f, "}}")?;
                 for variant in &details.variants {
+                    if variant.ty.is_none() {
+                        write!(
+                            f,
+                            "{}",
+                            UnitVariantUnmarshaller {
+                                tag_expr: "temp.Tag",
+                                variant,
+                                all_variants: &details.variants
+                            }
+                        )?;
+                        continue;
+                    }
                     write!(
                         f,
                         "{}",
@@ -269,13 +528,25 @@ impl fmt::Display for UnionUnmarshal<'_> {
                 let f = &mut indented(f);
                 writeln!(
                     f,
-                    "temp := struct{{\n\tTag string `json:\"{}\"`\n}}{{}}",
+                    "temp := struct {{\n\tTag string `json:\"{}\"`\n}}{{}}",
                     tag
                 )?;
                 writeln!(f, "if err := json.Unmarshal(data, &temp); err != nil {{")?;
                 writeln!(f, "\treturn err")?;
                 writeln!(f, "}}")?;
                 for variant in &details.variants {
+                    if variant.ty.is_none() {
+                        write!(
+                            f,
+                            "{}",
+                            UnitVariantUnmarshaller {
+                                tag_expr: "temp.Tag",
+                                variant,
+                                all_variants: &details.variants
+                            }
+                        )?;
+                        continue;
+                    }
                     write!(
                         f,
                         "{}",
@@ -290,17 +561,149 @@ impl fmt::Display for UnionUnmarshal<'_> {
                 writeln!(f, "}}")?;
                 writeln!(f, "return nil")?;
             }
-
-            _ => todo!("Support other enum representaitons"),
+            UnionRepresentation::ExternallyTagged => {
+                let f = &mut indented(f);
+                // Unit variants are serialized as a plain string, so we check for those first
+                if details.variants.iter().any(|v| v.ty.is_none()) {
+                    writeln!(f, "var tag string")?;
+                    writeln!(f, "if err := json.Unmarshal(data, &tag); err == nil {{")?;
+                    {
+                        let f = &mut indented(f);
+                        for variant in details.variants.iter().filter(|v| v.ty.is_none()) {
+                            write!(
+                                f,
+                                "{}",
+                                UnitVariantUnmarshaller {
+                                    tag_expr: "tag",
+                                    variant,
+                                    all_variants: &details.variants
+                                }
+                            )?;
+                        }
+                        writeln!(f, "{{")?;
+                        writeln!(indented(f), "return errors.New(\"Unknown type tag\")")?;
+                        writeln!(f, "}}")?;
+                        writeln!(f, "return nil")?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+                writedoc!(
+                    f,
+                    r#"
+                        temp := make(map[string]json.RawMessage)
+                        if err := json.Unmarshal(data, &temp); err != nil {{
+                            return err
+                        }}
+                        if len(temp) != 1 {{
+                            return errors.New("Expected a single type tag")
+                        }}
+                    "#
+                )?;
+                for variant in details.variants.iter().filter(|v| v.ty.is_some()) {
+                    write!(
+                        f,
+                        "{}",
+                        ExternallyTaggedVariantUnmarshaller {
+                            variant,
+                            all_variants: &details.variants
+                        }
+                    )?;
+                }
+                writeln!(f, "{{")?;
+                writeln!(indented(f), "return errors.New(\"Unknown type tag\")")?;
+                writeln!(f, "}}")?;
+                writeln!(f, "return nil")?;
+            }
+            UnionRepresentation::Untagged => {
+                let f = &mut indented(f);
+                // Untagged unit variants are serialized as null
+                if let Some(variant) = details.variants.iter().find(|v| v.ty.is_none()) {
+                    writedoc!(
+                        f,
+                        r#"
+                            var value interface{{}}
+                            if err := json.Unmarshal(data, &value); err != nil {{
+                                return err
+                            }}
+                            if value == nil {{
+                                self.{go_name} = true
+                        "#,
+                        go_name = variant.go_name()
+                    )?;
+                    write!(
+                        indented(f),
+                        "{}",
+                        ClearOtherVariants {
+                            variant,
+                            all_variants: &details.variants
+                        }
+                    )?;
+                    writeln!(indented(f), "return nil")?;
+                    writeln!(f, "}}")?;
+                }
+                for variant in details.variants.iter().filter(|v| v.ty.is_some()) {
+                    write!(
+                        f,
+                        "{}",
+                        UntaggedVariantUnmarshaller {
+                            variant,
+                            all_variants: &details.variants
+                        }
+                    )?;
+                }
+                writeln!(f, "return errors.New(\"Data did not match any variant\")")?;
+            }
         }
         writeln!(f, "}}")?;
 
-        // TODO: Support anything other than Adjacent tagging
-        //todo!("Write UnionUnmarshal")
         Ok(())
     }
 }
 
+/// Sets all the variants of a union other than `variant` to nil/false
+struct ClearOtherVariants<'a> {
+    variant: &'a UnionVariant,
+    all_variants: &'a [UnionVariant],
+}
+
+impl fmt::Display for ClearOtherVariants<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for other_variant in self.all_variants {
+            if other_variant == self.variant {
+                continue;
+            }
+            writeln!(f, "{}", other_variant.go_clear("self"))?;
+        }
+        Ok(())
+    }
+}
+
+struct UnitVariantUnmarshaller<'a> {
+    tag_expr: &'a str,
+    variant: &'a UnionVariant,
+    all_variants: &'a [UnionVariant],
+}
+
+impl fmt::Display for UnitVariantUnmarshaller<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "if {} == \"{}\" {{",
+            self.tag_expr, self.variant.serialized_name
+        )?;
+        writeln!(indented(f), "self.{} = true", self.variant.go_name())?;
+        write!(
+            indented(f),
+            "{}",
+            ClearOtherVariants {
+                variant: self.variant,
+                all_variants: self.all_variants
+            }
+        )?;
+        write!(f, "}} else ")
+    }
+}
+
 struct AdjacentlyTaggedVariantUnmarshaller<'a> {
     content: &'a str,
     variant: &'a UnionVariant,
@@ -313,7 +716,7 @@ impl fmt::Display for AdjacentlyTaggedVariantUnmarshaller<'_> {
         writeln!(
             indented(f),
             "rv := struct {{\n\tData {} `json:\"{}\"`\n}}{{}}",
-            self.variant.ty.go_type(),
+            self.variant.go_type(),
             self.content,
         )?;
         writeln!(
@@ -323,12 +726,14 @@ impl fmt::Display for AdjacentlyTaggedVariantUnmarshaller<'_> {
         writeln!(indented(f), "\treturn err")?;
         writeln!(indented(f), "}}")?;
         writeln!(indented(f), "self.{} = &rv.Data", self.variant.go_name())?;
-        for other_variant in self.all_variants {
-            if other_variant == self.variant {
-                continue;
+        write!(
+            indented(f),
+            "{}",
+            ClearOtherVariants {
+                variant: self.variant,
+                all_variants: self.all_variants
             }
-            writeln!(indented(f), "self.{} = nil", other_variant.go_name())?;
-        }
+        )?;
         write!(f, "}} else ")
     }
 }
@@ -351,33 +756,113 @@ impl fmt::Display for InternallyTaggedVariantUnmarshaller<'_> {
                 self.{go_name} = &rv
             "#,
             serialized_name = self.variant.serialized_name,
-            go_type = self.variant.ty.go_type(),
+            go_type = self.variant.go_type(),
             go_name = self.variant.go_name()
         )?;
-        for other_variant in self.all_variants {
-            if other_variant == self.variant {
-                continue;
+        write!(
+            indented(f),
+            "{}",
+            ClearOtherVariants {
+                variant: self.variant,
+                all_variants: self.all_variants
             }
-            writeln!(indented(f), "self.{} = nil", other_variant.go_name())?;
-        }
+        )?;
         write!(f, "}} else ")
     }
 }
 
-fn to_pascal_case(s: &str) -> String {
-    let mut buf = String::with_capacity(s.len());
-    let mut prev_is_underscore = true;
-    for c in s.chars() {
-        if c == '_' {
-            prev_is_underscore = true;
-        } else if prev_is_underscore {
-            buf.push(c.to_ascii_uppercase());
-            prev_is_underscore = false;
-        } else {
-            buf.push(c.to_ascii_lowercase());
+struct ExternallyTaggedVariantUnmarshaller<'a> {
+    variant: &'a UnionVariant,
+    all_variants: &'a [UnionVariant],
+}
+
+impl fmt::Display for ExternallyTaggedVariantUnmarshaller<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
+            r#"
+            if raw, ok := temp["{serialized_name}"]; ok {{
+                var rv {go_type}
+                if err := json.Unmarshal(raw, &rv); err != nil {{
+                    return err
+                }}
+                self.{go_name} = &rv
+            "#,
+            serialized_name = self.variant.serialized_name,
+            go_type = self.variant.go_type(),
+            go_name = self.variant.go_name()
+        )?;
+        write!(
+            indented(f),
+            "{}",
+            ClearOtherVariants {
+                variant: self.variant,
+                all_variants: self.all_variants
+            }
+        )?;
+        write!(f, "}} else ")
+    }
+}
+
+struct UntaggedVariantUnmarshaller<'a> {
+    variant: &'a UnionVariant,
+    all_variants: &'a [UnionVariant],
+}
+
+impl fmt::Display for UntaggedVariantUnmarshaller<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
+            r#"
+            {{
+                var rv {go_type}
+                if err := json.Unmarshal(data, &rv); err == nil {{
+                    self.{go_name} = &rv
+            "#,
+            go_type = self.variant.go_type(),
+            go_name = self.variant.go_name()
+        )?;
+        {
+            let f = &mut indented(f);
+            write!(
+                indented(f),
+                "{}",
+                ClearOtherVariants {
+                    variant: self.variant,
+                    all_variants: self.all_variants
+                }
+            )?;
+            writeln!(indented(f), "return nil")?;
+            writeln!(f, "}}")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Converts the variant names of a union into go identifiers.
+///
+/// Everything else works with the variant names, so it's easier to do this up front.
+fn go_union(details: &Union, options: &GoOptions) -> Union {
+    let mut details = details.clone();
+    let names = escape_variant_names(
+        details
+            .variants
+            .iter()
+            .map(|variant| {
+                let name = match &variant.name {
+                    Some(name) => options.identifier(name),
+                    None => variant.go_name(),
+                };
+                (name, variant.ty.is_some())
+            })
+            .collect(),
+    );
+    for (variant, name) in details.variants.iter_mut().zip(names) {
+        if variant.name.is_some() {
+            variant.name = Some(name);
         }
     }
-    buf
+    details
 }
 
 #[cfg(test)]
@@ -385,7 +870,7 @@ mod tests {
     use insta::assert_snapshot;
 
     use super::*;
-    use crate::types::TypeRef;
+    use crate::types::{IntKind, TypeRef};
 
     #[test]
     fn test_primitive_structs() {
@@ -397,31 +882,51 @@ mod tests {
                         name: "a_string".into(),
                         serialized_name: "a_string".into(),
                         ty: FieldType::Primitive(Primitive::String),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "an_int".into(),
                         serialized_name: "renamed_tho".into(),
-                        ty: FieldType::Primitive(Primitive::Int),
+                        ty: FieldType::Primitive(Primitive::Int(IntKind::I64)),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "a_bool".into(),
                         serialized_name: "also_renamed".into(),
                         ty: FieldType::Primitive(Primitive::Bool),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "a_float".into(),
                         serialized_name: "a_float".into(),
                         ty: FieldType::Primitive(Primitive::Float),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                 ],
             })
             .to_string(),
             @r###"
         type MyStruct struct {
-        	AString string `json:"a_string"`
-        	AnInt int `json:"renamed_tho"`
-        	ABool bool `json:"also_renamed"`
-        	AFloat float64 `json:"a_float"`
+        	AString string  `json:"a_string"`
+        	AnInt   int     `json:"renamed_tho"`
+        	ABool   bool    `json:"also_renamed"`
+        	AFloat  float64 `json:"a_float"`
+        }
+
+        func (v MyStruct) Validate() error {
+        	return validateMyStruct(v, "")
+        }
+
+        func validateMyStruct(s MyStruct, path string) error {
+        	return nil
         }
         "###
         );
@@ -433,8 +938,17 @@ mod tests {
             name: "UserId".into(),
             inner: FieldType::Primitive(Primitive::String),
         })
-        .to_string(), @"type UserId string
-");
+        .to_string(), @r###"
+        type UserId string
+
+        func (v UserId) Validate() error {
+        	return validateUserId(v, "")
+        }
+
+        func validateUserId(n UserId, path string) error {
+        	return nil
+        }
+        "###);
     }
 
     #[test]
@@ -456,9 +970,55 @@ mod tests {
         type FulfilmentType string
 
         const (
-        	FulfilmentTypeDelivery FulfilmentType = "DELIVERY"
+        	FulfilmentTypeDelivery   FulfilmentType = "DELIVERY"
         	FulfilmentTypeCollection FulfilmentType = "COLLECTION"
         )
+
+        func AllFulfilmentTypeValues() []FulfilmentType {
+        	return []FulfilmentType{
+        		FulfilmentTypeDelivery,
+        		FulfilmentTypeCollection,
+        	}
+        }
+
+        func (e FulfilmentType) IsValid() bool {
+        	switch e {
+        	case FulfilmentTypeDelivery, FulfilmentTypeCollection:
+        		return true
+        	}
+        	return false
+        }
+
+        func (e FulfilmentType) String() string {
+        	return string(e)
+        }
+
+        func (e *FulfilmentType) UnmarshalJSON(data []byte) error {
+        	var value string
+        	if err := json.Unmarshal(data, &value); err != nil {
+        		return err
+        	}
+        	if !FulfilmentType(value).IsValid() {
+        		return fmt.Errorf("unknown FulfilmentType value: %q", value)
+        	}
+        	*e = FulfilmentType(value)
+        	return nil
+        }
+
+        func (v FulfilmentType) Validate() error {
+        	return validateFulfilmentType(v, "")
+        }
+
+        func validateFulfilmentType(e FulfilmentType, path string) error {
+        	if !e.IsValid() {
+        		err := fmt.Errorf("unknown FulfilmentType value: %q", string(e))
+        		if path != "" {
+        			err = fmt.Errorf("%s: %w", path, err)
+        		}
+        		return err
+        	}
+        	return nil
+        }
         "###);
     }
 
@@ -473,16 +1033,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarOne".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                     serialized_name: "VAR_ONE".into(),
                 },
                 UnionVariant {
                     name: Some("VarTwo".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_TWO".into(),
                 }
             ]
@@ -490,6 +1046,44 @@ mod tests {
         .to_string());
     }
 
+    #[test]
+    fn test_unnamed_union_variants() {
+        assert_snapshot!(GoType::Union(&Union {
+            name: "MyUnion".into(),
+            representation: UnionRepresentation::ExternallyTagged,
+            variants: vec![
+                UnionVariant {
+                    name: None,
+                    ty: Some(FieldType::Primitive(Primitive::String)),
+                    serialized_name: "text".into(),
+                },
+                UnionVariant {
+                    name: None,
+                    ty: Some(FieldType::List(Box::new(FieldType::Named(TypeRef::new(
+                        "VarTwo"
+                    ))))),
+                    serialized_name: "many-items".into(),
+                },
+                UnionVariant {
+                    name: None,
+                    ty: None,
+                    serialized_name: "2fa".into(),
+                }
+            ]
+        })
+        .to_string());
+    }
+
+    #[test]
+    fn test_go_quote() {
+        assert_eq!(go_quote("plain"), r#""plain""#);
+        assert_eq!(go_quote("naïve ✓"), r#""naïve ✓""#);
+        assert_eq!(
+            go_quote("a \"b\" \\ `c`\n\u{0}\u{85}"),
+            r#""a \"b\" \\ \x60c\x60\n\x00\u0085""#
+        );
+    }
+
     #[test]
     fn test_list_types() {
         assert_snapshot!(
@@ -503,7 +1097,7 @@ mod tests {
         assert_snapshot!(
             FieldType::Map{
                 key: Box::new(FieldType::Primitive(Primitive::String)),
-                value: Box::new(FieldType::Primitive(Primitive::Int))
+                value: Box::new(FieldType::Primitive(Primitive::Int(IntKind::I64)))
             }.go_type(),
             @"map[string]int"
         );
@@ -0,0 +1,231 @@
+/// Options for customising the generated go code.
+///
+/// Use with `registry_to_output_with_options::<GoType>`.
+#[derive(Clone, Debug)]
+pub struct GoOptions {
+    initialisms: Vec<String>,
+    pub(super) preserve_unknown_enum_values: bool,
+    pub(super) union_style: GoUnionStyle,
+    pub(super) tag_keys: Vec<String>,
+    pub(super) omit_empty: GoOmitEmpty,
+    pub(super) validate_required: bool,
+}
+
+/// Which struct fields get `omitempty` added to their tags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GoOmitEmpty {
+    /// Never add `omitempty`
+    #[default]
+    Never,
+    /// Add `omitempty` to fields that are optional in rust
+    Optional,
+    /// Add `omitempty` to every field
+    Always,
+}
+
+/// How enums with data are represented in go
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GoUnionStyle {
+    /// A struct with a pointer field for each variant, exactly one of which should be set.
+    #[default]
+    Struct,
+    /// A struct holding a single value of a sealed interface, which is implemented by a
+    /// type for each variant.
+    ///
+    /// For a union `Payment` this generates `type Payment struct { Value PaymentVariant }`,
+    /// an interface `PaymentVariant` and a type per variant, e.g. `PaymentCard`.  This
+    /// makes it impossible to set more than one variant at once.
+    SealedInterface,
+}
+
+/// The initialisms that golint expects to be all caps.
+const DEFAULT_INITIALISMS: &[&str] = &[
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "LHS", "QPS", "RAM", "RHS", "RPC", "SLA", "SMTP", "SQL", "SSH", "TCP", "TLS", "TTL",
+    "UDP", "UI", "UID", "UUID", "URI", "URL", "UTF8", "VM", "XML", "XMPP", "XSRF", "XSS",
+];
+
+impl Default for GoOptions {
+    fn default() -> Self {
+        GoOptions {
+            initialisms: DEFAULT_INITIALISMS.iter().map(|s| s.to_string()).collect(),
+            preserve_unknown_enum_values: false,
+            union_style: GoUnionStyle::default(),
+            tag_keys: Vec::new(),
+            omit_empty: GoOmitEmpty::default(),
+            validate_required: false,
+        }
+    }
+}
+
+impl GoOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the list of initialisms that are written in all caps in go identifiers.
+    ///
+    /// This defaults to the list used by golint, e.g. `ID`, `URL`, `HTTP` & `JSON`.
+    pub fn with_initialisms<I>(mut self, initialisms: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.initialisms = initialisms
+            .into_iter()
+            .map(|s| s.into().to_uppercase())
+            .collect();
+        self
+    }
+
+    /// Adds to the list of initialisms that are written in all caps in go identifiers.
+    pub fn with_extra_initialisms<I>(mut self, initialisms: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.initialisms
+            .extend(initialisms.into_iter().map(|s| s.into().to_uppercase()));
+        self
+    }
+
+    /// Controls whether string enums accept values they don't know about.
+    ///
+    /// By default the generated `UnmarshalJSON` rejects unknown values.  When this is
+    /// enabled unknown values are kept as-is so that newer servers can add variants
+    /// without breaking older clients.  `IsValid` still returns false for these values,
+    /// but `Validate` will accept them.
+    pub fn with_preserve_unknown_enum_values(mut self, preserve: bool) -> Self {
+        self.preserve_unknown_enum_values = preserve;
+        self
+    }
+
+    /// Sets how enums with data are represented in go.
+    ///
+    /// Defaults to `GoUnionStyle::Struct`.
+    pub fn with_union_style(mut self, style: GoUnionStyle) -> Self {
+        self.union_style = style;
+        self
+    }
+
+    /// Adds struct tags with these keys to every field, using the same name as the `json` tag.
+    ///
+    /// For example `with_tag_keys(["yaml", "mapstructure"])` outputs fields like
+    /// ``Name string `json:"name" yaml:"name" mapstructure:"name"` ``
+    pub fn with_tag_keys<I>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.tag_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets which fields get `omitempty` added to their `json` & other name tags.
+    ///
+    /// Defaults to `GoOmitEmpty::Never`.
+    pub fn with_omit_empty(mut self, omit_empty: GoOmitEmpty) -> Self {
+        self.omit_empty = omit_empty;
+        self
+    }
+
+    /// Controls whether non-optional fields get a `validate:"required"` tag, for use with
+    /// `github.com/go-playground/validator`.
+    pub fn with_validate_required(mut self, validate_required: bool) -> Self {
+        self.validate_required = validate_required;
+        self
+    }
+
+    /// Converts a rust identifier (in snake_case, camelCase or PascalCase) into an exported
+    /// go identifier, e.g. `api_url` becomes `APIURL`.
+    pub(super) fn identifier(&self, s: &str) -> String {
+        let mut buf = String::with_capacity(s.len());
+        for word in split_words(s) {
+            let upper = word.to_uppercase();
+            if self.initialisms.contains(&upper) {
+                buf.push_str(&upper);
+                continue;
+            }
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                buf.push(first.to_ascii_uppercase());
+                buf.extend(chars.map(|c| c.to_ascii_lowercase()));
+            }
+        }
+        buf
+    }
+
+    /// Converts a rust identifier into an unexported go identifier, e.g. `user_id` becomes
+    /// `userID` and `id` becomes `id`.
+    pub(super) fn unexported_identifier(&self, s: &str) -> String {
+        let mut words = split_words(s).into_iter();
+        let first = words.next().unwrap_or_default().to_lowercase();
+        let rest = words.collect::<Vec<_>>().join("_");
+        first + &self.identifier(&rest)
+    }
+}
+
+/// Splits an identifier into words on underscores & case changes.
+///
+/// A run of capitals is treated as a single word, so `HTTPServer` is `HTTP` & `Server`.
+fn split_words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in s.split('_').filter(|part| !part.is_empty()) {
+        let chars = part.char_indices().collect::<Vec<_>>();
+        let mut start = 0;
+        for window in 0..chars.len() {
+            let (index, current) = chars[window];
+            if index == start {
+                continue;
+            }
+            let (_, prev) = chars[window - 1];
+            let next = chars.get(window + 1).map(|(_, c)| *c);
+            let lower_to_upper =
+                (prev.is_lowercase() || prev.is_ascii_digit()) && current.is_uppercase();
+            let end_of_acronym = prev.is_uppercase()
+                && current.is_uppercase()
+                && next.map(|c| c.is_lowercase()).unwrap_or(false);
+            if lower_to_upper || end_of_acronym {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers() {
+        let options = GoOptions::default();
+
+        assert_eq!(options.identifier("user_id"), "UserID");
+        assert_eq!(options.identifier("api_url"), "APIURL");
+        assert_eq!(options.identifier("userID"), "UserID");
+        assert_eq!(options.unexported_identifier("user_id"), "userID");
+        assert_eq!(options.unexported_identifier("id"), "id");
+        assert_eq!(options.unexported_identifier("HTTPServer"), "httpServer");
+        assert_eq!(options.identifier("UserId"), "UserID");
+        assert_eq!(options.identifier("HTTPServer"), "HTTPServer");
+        assert_eq!(options.identifier("json_payload"), "JSONPayload");
+        assert_eq!(options.identifier("a_string"), "AString");
+        assert_eq!(options.identifier("fulfilment_type"), "FulfilmentType");
+        assert_eq!(options.identifier("utf8_name"), "UTF8Name");
+        assert_eq!(options.identifier("identity"), "Identity");
+    }
+
+    #[test]
+    fn test_custom_initialisms() {
+        let options = GoOptions::new()
+            .with_initialisms(["id"])
+            .with_extra_initialisms(["sku"]);
+
+        assert_eq!(options.identifier("product_sku"), "ProductSKU");
+        assert_eq!(options.identifier("api_id"), "ApiID");
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::{self, Write};
+
+use indoc::writedoc;
+
+use super::{indented, validate::ValidateMethod, StructUnion, Union, UnionVariant, CELL};
+
+/// Writes a union as a struct holding a sealed interface, with a type for each variant.
+///
+/// The marshalling & validation is handled by converting to & from the struct style
+/// union, which we also output under an unexported name.
+pub struct SealedUnion<'a>(pub &'a Union);
+
+impl fmt::Display for SealedUnion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        let name = &details.name;
+        let fields = fields_name(details);
+
+        writedoc!(
+            f,
+            r#"
+                type {name} struct {{
+                    Value {name}Variant
+                }}
+
+                type {name}Variant interface {{
+                    is{name}Variant()
+                }}
+
+            "#
+        )?;
+        for variant in &details.variants {
+            let variant_type = variant_type_name(details, variant);
+            match variant.ty {
+                // Methods can't be declared on named pointer types, so pointers are
+                // wrapped in a struct
+                Some(_) if is_pointer(variant) => {
+                    writeln!(f, "type {variant_type} struct {{")?;
+                    writeln!(indented(f), "Value{CELL}{}", variant.go_type())?;
+                    writeln!(f, "}}\n")?;
+                }
+                Some(_) => writeln!(f, "type {variant_type} {}\n", variant.go_type())?,
+                None => writeln!(f, "type {variant_type} struct{{}}\n")?,
+            }
+            writeln!(f, "func ({variant_type}) is{name}Variant() {{}}\n")?;
+        }
+
+        writeln!(f, "func (u {name}) toJSON() {fields} {{")?;
+        writeln!(indented(f), "var fields {fields}")?;
+        writeln!(indented(f), "switch {} {{", type_switch(details))?;
+        for variant in &details.variants {
+            let f = &mut indented(f);
+            writeln!(f, "case {}:", variant_type_name(details, variant))?;
+            match variant.ty {
+                Some(_) => {
+                    writeln!(f, "\tinner := {}", unwrap_variant(variant, "value"))?;
+                    writeln!(f, "\tfields.{} = &inner", variant.go_name())?;
+                }
+                None => writeln!(f, "\tfields.{} = true", variant.go_name())?,
+            }
+        }
+        writeln!(indented(f), "}}")?;
+        writeln!(indented(f), "return fields")?;
+        writeln!(f, "}}\n")?;
+
+        writeln!(f, "func (fields {fields}) variant() {name}Variant {{")?;
+        for variant in &details.variants {
+            let variant_type = variant_type_name(details, variant);
+            writeln!(indented(f), "if {} {{", variant.go_is_set("fields"))?;
+            match variant.ty {
+                Some(_) => writeln!(
+                    indented(f),
+                    "\treturn {}",
+                    wrap_variant(details, variant, &format!("*fields.{}", variant.go_name()))
+                )?,
+                None => writeln!(indented(f), "\treturn {variant_type}{{}}")?,
+            }
+            writeln!(indented(f), "}}")?;
+        }
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}\n")?;
+
+        writedoc!(
+            f,
+            r#"
+                func (self {name}) MarshalJSON() ([]byte, error) {{
+                    return json.Marshal(self.toJSON())
+                }}
+
+                func (self *{name}) UnmarshalJSON(data []byte) error {{
+                    var fields {fields}
+                    if err := json.Unmarshal(data, &fields); err != nil {{
+                        return err
+                    }}
+                    self.Value = fields.variant()
+                    return nil
+                }}
+
+            "#
+        )?;
+
+        write!(f, "{}", ValidateMethod(name))?;
+        writedoc!(
+            f,
+            r#"
+                func validate{name}(u {name}, path string) error {{
+                    return validate{fields}(u.toJSON(), path)
+                }}
+
+            "#
+        )?;
+
+        let mut fields_union = details.clone();
+        fields_union.name = fields;
+        write!(f, "{}", StructUnion(&fields_union))
+    }
+}
+
+/// The name of the unexported struct style union used for marshalling
+fn fields_name(details: &Union) -> String {
+    format!("json{}", details.name)
+}
+
+/// The name of the go type for a variant of a sealed interface union
+pub(super) fn variant_type_name(details: &Union, variant: &UnionVariant) -> String {
+    format!("{}{}", details.name, variant.go_name())
+}
+
+/// Whether the data of `variant` is a pointer, which needs wrapping in a struct
+fn is_pointer(variant: &UnionVariant) -> bool {
+    variant.go_type().starts_with('*')
+}
+
+/// Converts `expr`, which holds the data of a variant, into the variants type
+pub(super) fn wrap_variant(details: &Union, variant: &UnionVariant, expr: &str) -> String {
+    let variant_type = variant_type_name(details, variant);
+    match is_pointer(variant) {
+        true => format!("{variant_type}{{Value: {expr}}}"),
+        false => format!("{variant_type}({expr})"),
+    }
+}
+
+/// Converts `expr`, which holds a variant type, back into the type of the variants data
+pub(super) fn unwrap_variant(variant: &UnionVariant, expr: &str) -> String {
+    match is_pointer(variant) {
+        true => format!("{expr}.Value"),
+        false => format!("{}({expr})", variant.go_type()),
+    }
+}
+
+/// The head of a type switch on the value of a sealed union `u`.
+///
+/// This only binds `value` if a variant has data that could use it, as go doesn't
+/// allow unused variables.
+pub(super) fn type_switch(details: &Union) -> &'static str {
+    if details.variants.iter().any(|variant| variant.ty.is_some()) {
+        "value := u.Value.(type)"
+    } else {
+        "u.Value.(type)"
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::{self, Write};
+
+use indoc::writedoc;
+
+use super::{
+    go_quote, indented,
+    sealed::{type_switch, unwrap_variant, variant_type_name, wrap_variant},
+    GoUnionStyle, Union, CELL,
+};
+
+/// Writes helpers that make a union easier to use from go: constructors, accessors,
+/// a `Kind` method & a visitor interface.
+///
+/// Sealed interface unions don't get accessors, as a type switch does the same job.
+pub struct UnionHelpers<'a>(pub &'a Union, pub GoUnionStyle);
+
+impl UnionHelpers<'_> {
+    fn kind_name(&self) -> String {
+        format!("{}Kind", self.0.name)
+    }
+
+    fn visitor_name(&self) -> String {
+        format!("{}Visitor", self.0.name)
+    }
+}
+
+impl fmt::Display for UnionHelpers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        let kind = self.kind_name();
+
+        writeln!(f, "\ntype {kind} string\n")?;
+        writeln!(f, "const (")?;
+        for variant in &details.variants {
+            writeln!(
+                indented(f),
+                "{kind}{}{CELL}{kind}{CELL}= {}",
+                variant.go_name(),
+                go_quote(&variant.serialized_name)
+            )?;
+        }
+        writeln!(f, ")\n")?;
+
+        match self.1 {
+            GoUnionStyle::Struct => write!(f, "{}", StructConstructors(details))?,
+            GoUnionStyle::SealedInterface => write!(f, "{}", SealedConstructors(details))?,
+        }
+
+        writeln!(f, "func (u {}) Kind() {kind} {{", details.name)?;
+        match self.1 {
+            GoUnionStyle::Struct => {
+                for variant in &details.variants {
+                    writeln!(indented(f), "if {} {{", variant.go_is_set("u"))?;
+                    writeln!(indented(f), "\treturn {kind}{}", variant.go_name())?;
+                    writeln!(indented(f), "}}")?;
+                }
+            }
+            GoUnionStyle::SealedInterface => {
+                writeln!(indented(f), "switch u.Value.(type) {{")?;
+                for variant in &details.variants {
+                    let f = &mut indented(f);
+                    writeln!(f, "case {}:", variant_type_name(details, variant))?;
+                    writeln!(f, "\treturn {kind}{}", variant.go_name())?;
+                }
+                writeln!(indented(f), "}}")?;
+            }
+        }
+        writeln!(indented(f), "return \"\"")?;
+        writeln!(f, "}}\n")?;
+
+        let visitor = self.visitor_name();
+        writeln!(f, "type {visitor} interface {{")?;
+        for variant in &details.variants {
+            match variant.ty {
+                Some(_) => writeln!(
+                    indented(f),
+                    "Visit{}(v {}) error",
+                    variant.go_name(),
+                    variant.go_type()
+                )?,
+                None => writeln!(indented(f), "Visit{}() error", variant.go_name())?,
+            }
+        }
+        writeln!(f, "}}\n")?;
+
+        writeln!(
+            f,
+            "func (u {}) Visit(visitor {visitor}) error {{",
+            details.name
+        )?;
+        match self.1 {
+            GoUnionStyle::Struct => {
+                for variant in &details.variants {
+                    writeln!(indented(f), "if {} {{", variant.go_is_set("u"))?;
+                    match variant.ty {
+                        Some(_) => writeln!(
+                            indented(f),
+                            "\treturn visitor.Visit{name}(*u.{name})",
+                            name = variant.go_name()
+                        )?,
+                        None => {
+                            writeln!(indented(f), "\treturn visitor.Visit{}()", variant.go_name())?
+                        }
+                    }
+                    writeln!(indented(f), "}}")?;
+                }
+            }
+            GoUnionStyle::SealedInterface => {
+                writeln!(indented(f), "switch {} {{", type_switch(details))?;
+                for variant in &details.variants {
+                    let f = &mut indented(f);
+                    writeln!(f, "case {}:", variant_type_name(details, variant))?;
+                    match variant.ty {
+                        Some(_) => writeln!(
+                            f,
+                            "\treturn visitor.Visit{}({})",
+                            variant.go_name(),
+                            unwrap_variant(variant, "value")
+                        )?,
+                        None => writeln!(f, "\treturn visitor.Visit{}()", variant.go_name())?,
+                    }
+                }
+                writeln!(indented(f), "}}")?;
+            }
+        }
+        writeln!(
+            indented(f),
+            "return fmt.Errorf(\"no variant of {} was populated\")",
+            details.name
+        )?;
+        writeln!(f, "}}")
+    }
+}
+
+struct StructConstructors<'a>(&'a Union);
+
+impl fmt::Display for StructConstructors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        for variant in &details.variants {
+            match variant.ty {
+                Some(_) => writedoc!(
+                    f,
+                    r#"
+                        func New{name}{variant}(v {ty}) {name} {{
+                            return {name}{{{variant}: &v}}
+                        }}
+
+                        func (u {name}) As{variant}() ({ty}, bool) {{
+                            if u.{variant} == nil {{
+                                var empty {ty}
+                                return empty, false
+                            }}
+                            return *u.{variant}, true
+                        }}
+
+                    "#,
+                    name = details.name,
+                    variant = variant.go_name(),
+                    ty = variant.go_type(),
+                )?,
+                None => writedoc!(
+                    f,
+                    r#"
+                        func New{name}{variant}() {name} {{
+                            return {name}{{{variant}: true}}
+                        }}
+
+                    "#,
+                    name = details.name,
+                    variant = variant.go_name(),
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct SealedConstructors<'a>(&'a Union);
+
+impl fmt::Display for SealedConstructors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        for variant in &details.variants {
+            match variant.ty {
+                Some(_) => writedoc!(
+                    f,
+                    r#"
+                        func New{name}{variant}(v {ty}) {name} {{
+                            return {name}{{Value: {value}}}
+                        }}
+
+                    "#,
+                    name = details.name,
+                    variant = variant.go_name(),
+                    ty = variant.go_type(),
+                    value = wrap_variant(details, variant, "v"),
+                )?,
+                None => writedoc!(
+                    f,
+                    r#"
+                        func New{name}{variant}() {name} {{
+                            return {name}{{Value: {variant_type}{{}}}}
+                        }}
+
+                    "#,
+                    name = details.name,
+                    variant = variant.go_name(),
+                    variant_type = variant_type_name(details, variant),
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,42 +2,348 @@ use std::fmt::{self, Write};
 
 use indoc::writedoc;
 
-use super::{indented, Union};
+use super::{
+    go_field_name, go_quote, indented, Enum, FieldType, GoOptions, Struct, Union,
+    UnionRepresentation,
+};
+
+/// Writes the validation for a struct.
+///
+/// Every type we output gets a `validate<Name>(value, path)` function that checks
+/// the value & everything inside it, reporting the JSON path of the first problem it
+/// finds.  Most types also get a `Validate()` method that calls this with an empty path.
+pub struct StructValidate<'a>(pub &'a Struct, pub &'a GoOptions);
+
+impl fmt::Display for StructValidate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let StructValidate(details, options) = self;
+        write!(f, "{}", ValidateMethod(&details.name))?;
+        writeln!(
+            f,
+            "func validate{name}(s {name}, path string) error {{",
+            name = details.name
+        )?;
+        let fields = details
+            .fields
+            .iter()
+            .filter(|field| field.ty.needs_validation())
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            write!(indented(f), "{}", PathPrefix)?;
+        }
+        for field in fields {
+            let expr = format!("s.{}", go_field_name(field, options));
+            let path = format!("prefix+{}", go_quote(&field.serialized_name));
+            match &field.ty {
+                // Recursive fields are behind a pointer, see `go_field_type`
+                FieldType::Named(type_ref) if type_ref.is_recursive() => {
+                    writeln!(indented(f), "if {expr} != nil {{")?;
+                    write!(
+                        indented(&mut indented(f)),
+                        "{}",
+                        ValidateValue::new(format!("*{expr}"), path, &field.ty)
+                    )?;
+                    writeln!(indented(f), "}}")?;
+                }
+                ty => write!(indented(f), "{}", ValidateValue::new(expr, path, ty))?,
+            }
+        }
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}")
+    }
+}
 
 pub struct UnionValidate<'a>(pub &'a Union);
 
 impl fmt::Display for UnionValidate<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let details = self.0;
+        write!(f, "{}", ValidateMethod(&details.name))?;
         writeln!(
             f,
-            "func (u {union_name}) Validate() error {{",
-            union_name = self.0.name
+            "func validate{union_name}(u {union_name}, path string) error {{",
+            union_name = details.name
         )?;
         writeln!(indented(f), "var count int\n")?;
-        for variant in &self.0.variants {
+        for variant in &details.variants {
             let f = &mut indented(f);
             writedoc!(
                 f,
                 r#"
-                    if u.{variant_name} != nil {{
+                    if {is_set} {{
                         count++
                     }}
 
                     "#,
-                variant_name = variant.go_name()
+                is_set = variant.go_is_set("u")
             )?;
         }
-        writedoc!(
+        writeln!(indented(f), "if count != 1 {{")?;
+        write!(
+            indented(&mut indented(f)),
+            "{}",
+            PathError(r#"fmt.Errorf("one variant must be populated, found %d", count)"#)
+        )?;
+        writeln!(indented(f), "}}\n")?;
+
+        let variants = details
+            .variants
+            .iter()
+            .filter_map(|variant| Some((variant, variant.ty.as_ref()?)))
+            .filter(|(_, ty)| ty.needs_validation())
+            .collect::<Vec<_>>();
+        let has_prefix = matches!(
+            details.representation,
+            UnionRepresentation::AdjacentlyTagged { .. } | UnionRepresentation::ExternallyTagged
+        );
+        if has_prefix && !variants.is_empty() {
+            write!(indented(f), "{}", PathPrefix)?;
+        }
+        for (variant, ty) in variants {
+            let path = match &details.representation {
+                UnionRepresentation::AdjacentlyTagged { content, .. } => {
+                    format!("prefix+{}", go_quote(content))
+                }
+                UnionRepresentation::ExternallyTagged => {
+                    format!("prefix+{}", go_quote(&variant.serialized_name))
+                }
+                UnionRepresentation::InternallyTagged { .. } | UnionRepresentation::Untagged => {
+                    "path".to_string()
+                }
+            };
+            writeln!(indented(f), "if {} {{", variant.go_is_set("u"))?;
+            write!(
+                indented(&mut indented(f)),
+                "{}",
+                ValidateValue::new(format!("*u.{}", variant.go_name()), path, ty)
+            )?;
+            writeln!(indented(f), "}}")?;
+        }
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}")
+    }
+}
+
+pub struct EnumValidate<'a>(pub &'a Enum, pub &'a GoOptions);
+
+impl fmt::Display for EnumValidate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let EnumValidate(details, options) = self;
+        write!(f, "{}", ValidateMethod(&details.name))?;
+        writeln!(
+            f,
+            "func validate{name}(e {name}, path string) error {{",
+            name = details.name
+        )?;
+        if !options.preserve_unknown_enum_values {
+            writeln!(indented(f), "if !e.IsValid() {{")?;
+            write!(
+                indented(&mut indented(f)),
+                "{}",
+                PathError(&format!(
+                    r#"fmt.Errorf("unknown {} value: %q", string(e))"#,
+                    details.name
+                ))
+            )?;
+            writeln!(indented(f), "}}")?;
+        }
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}")
+    }
+}
+
+/// Writes the validation for a newtype or alias of `inner`
+pub struct NewTypeValidate<'a> {
+    pub name: &'a str,
+    pub inner: &'a FieldType,
+}
+
+impl fmt::Display for NewTypeValidate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let NewTypeValidate { name, inner } = self;
+        // Go doesn't allow methods on named pointer types, but the function still works
+        if !matches!(inner, FieldType::Optional(_)) {
+            write!(f, "{}", ValidateMethod(name))?;
+        }
+        writeln!(f, "func validate{name}(n {name}, path string) error {{")?;
+        let expr = match inner {
+            // Go needs an explicit conversion to pass a newtype as its inner type
+            FieldType::Named(_) => format!("{}(n)", inner.go_type()),
+            _ => "n".to_string(),
+        };
+        write!(
             indented(f),
+            "{}",
+            ValidateValue::new(expr, "path".into(), inner)
+        )?;
+        writeln!(indented(f), "return nil")?;
+        writeln!(f, "}}")
+    }
+}
+
+impl FieldType {
+    /// Whether values of this type might contain something that needs validated
+    fn needs_validation(&self) -> bool {
+        match self {
+            FieldType::Named(_) => true,
+            FieldType::Optional(inner) | FieldType::List(inner) => inner.needs_validation(),
+            FieldType::Map { value, .. } => value.needs_validation(),
+            FieldType::Primitive(_) => false,
+        }
+    }
+}
+
+/// Writes the public `Validate` method for a type
+pub struct ValidateMethod<'a>(pub &'a str);
+
+impl fmt::Display for ValidateMethod<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
             r#"
-                if count != 1 {{
-                    return fmt.Errorf("one variant must be populated, found %d", count)
+                func (v {name}) Validate() error {{
+                    return validate{name}(v, "")
                 }}
 
-                return nil
+            "#,
+            name = self.0
+        )
+    }
+}
+
+/// Declares a `prefix` variable that child paths can be appended to
+struct PathPrefix;
+
+impl fmt::Display for PathPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
+            r#"
+                prefix := path
+                if prefix != "" {{
+                    prefix += "."
+                }}
             "#
-        )?;
-        writeln!(f, "}}")
+        )
+    }
+}
+
+/// Returns the error produced by a go expression, annotated with the current path
+struct PathError<'a>(&'a str);
+
+impl fmt::Display for PathError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
+            r#"
+                err := {}
+                if path != "" {{
+                    err = fmt.Errorf("%s: %w", path, err)
+                }}
+                return err
+            "#,
+            self.0
+        )
+    }
+}
+
+/// Validates the go expression `expr` of type `ty`, with the JSON path given by
+/// the go expression `path`
+struct ValidateValue<'a> {
+    expr: String,
+    path: String,
+    ty: &'a FieldType,
+    depth: usize,
+}
+
+impl<'a> ValidateValue<'a> {
+    fn new(expr: String, path: String, ty: &'a FieldType) -> Self {
+        ValidateValue {
+            expr,
+            path,
+            ty,
+            depth: 0,
+        }
+    }
+
+    fn nested(&self, expr: String, path: String, ty: &'a FieldType) -> Self {
+        ValidateValue {
+            expr,
+            path,
+            ty,
+            depth: self.depth + 1,
+        }
+    }
+
+    /// A name for a loop variable that won't shadow any outer loops
+    fn var(&self, name: &str) -> String {
+        match self.depth {
+            0 => name.to_string(),
+            depth => format!("{name}{depth}"),
+        }
+    }
+}
+
+impl fmt::Display for ValidateValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ValidateValue { expr, path, .. } = self;
+        match self.ty {
+            _ if !self.ty.needs_validation() => Ok(()),
+            FieldType::Named(type_ref) => writedoc!(
+                f,
+                r#"
+                    if err := validate{name}({expr}, {path}); err != nil {{
+                        return err
+                    }}
+                "#,
+                name = type_ref.name(),
+            ),
+            FieldType::Optional(inner) => {
+                writeln!(f, "if {expr} != nil {{")?;
+                // No new variables are declared here so there's no need to increase the depth
+                let inner = ValidateValue {
+                    expr: format!("*{expr}"),
+                    path: path.clone(),
+                    ty: inner,
+                    depth: self.depth,
+                };
+                write!(indented(f), "{inner}")?;
+                writeln!(f, "}}")
+            }
+            FieldType::List(inner) => {
+                let (index, item) = (self.var("i"), self.var("item"));
+                writeln!(f, "for {index}, {item} := range {expr} {{")?;
+                write!(
+                    indented(f),
+                    "{}",
+                    self.nested(
+                        item,
+                        format!(r#"fmt.Sprintf("%s[%d]", {path}, {index})"#),
+                        inner
+                    )
+                )?;
+                writeln!(f, "}}")
+            }
+            FieldType::Map { key, value } => {
+                let (key_var, value_var) = (self.var("key"), self.var("value"));
+                let verb = match **key {
+                    FieldType::Primitive(super::Primitive::String) => "%q",
+                    _ => "%v",
+                };
+                writeln!(f, "for {key_var}, {value_var} := range {expr} {{")?;
+                write!(
+                    indented(f),
+                    "{}",
+                    self.nested(
+                        value_var,
+                        format!(r#"fmt.Sprintf("%s[{verb}]", {path}, {key_var})"#),
+                        value
+                    )
+                )?;
+                writeln!(f, "}}")
+            }
+            FieldType::Primitive(_) => Ok(()),
+        }
     }
 }
 
@@ -61,16 +367,12 @@ mod tests {
                 variants: vec![
                     UnionVariant {
                         name: Some("VarOne".into()),
-                        ty: FieldType::Named(TypeRef {
-                            name: "VarOne".into()
-                        }),
+                        ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                         serialized_name: "VAR_ONE".into(),
                     },
                     UnionVariant {
                         name: Some("VarTwo".into()),
-                        ty: FieldType::Named(TypeRef {
-                            name: "VarTwo".into()
-                        }),
+                        ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                         serialized_name: "VAR_TWO".into(),
                     }
                 ]
@@ -78,7 +380,11 @@ mod tests {
         )
         .to_string(),
         @r###"
-        func (u MyUnion) Validate() error {
+        func (v MyUnion) Validate() error {
+            return validateMyUnion(v, "")
+        }
+
+        func validateMyUnion(u MyUnion, path string) error {
             var count int
 
             if u.VarOne != nil {
@@ -90,9 +396,27 @@ mod tests {
             }
 
             if count != 1 {
-                return fmt.Errorf("one variant must be populated, found %d", count)
+                err := fmt.Errorf("one variant must be populated, found %d", count)
+                if path != "" {
+                    err = fmt.Errorf("%s: %w", path, err)
+                }
+                return err
             }
 
+            prefix := path
+            if prefix != "" {
+                prefix += "."
+            }
+            if u.VarOne != nil {
+                if err := validateVarOne(*u.VarOne, prefix+"data"); err != nil {
+                    return err
+                }
+            }
+            if u.VarTwo != nil {
+                if err := validateVarTwo(*u.VarTwo, prefix+"data"); err != nil {
+                    return err
+                }
+            }
             return nil
         }
         "###
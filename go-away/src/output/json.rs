@@ -0,0 +1,268 @@
+//! A minimal JSON document model, for output formats that are JSON documents rather than code.
+
+use std::fmt::{self, Write};
+
+/// A JSON value.
+///
+/// Objects keep their keys in the order they were added, so documents come out in a
+/// predictable order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Bool(bool),
+    Int(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Creates an object from some `(key, value)` pairs
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Creates a string
+    pub fn string(value: impl Into<String>) -> Json {
+        Json::String(value.into())
+    }
+
+    /// Adds an entry to an object.
+    ///
+    /// Panics if this isn't an object.
+    pub fn insert(&mut self, key: impl Into<String>, value: Json) {
+        match self {
+            Json::Object(entries) => entries.push((key.into(), value)),
+            other => panic!("Can't insert into {:?}", other),
+        }
+    }
+
+    /// Outputs this value as pretty printed JSON, indented with 2 spaces
+    pub fn to_json(&self) -> String {
+        let mut output = String::new();
+        self.write_json(&mut output, 0).unwrap();
+        output.push('\n');
+        output
+    }
+
+    /// Outputs this value as YAML
+    pub fn to_yaml(&self) -> String {
+        let mut output = String::new();
+        match self {
+            Json::Array(items) if !items.is_empty() => self.write_yaml_block(&mut output, 0),
+            Json::Object(entries) if !entries.is_empty() => self.write_yaml_block(&mut output, 0),
+            scalar => writeln!(output, "{}", scalar.scalar()),
+        }
+        .unwrap();
+        output
+    }
+
+    fn write_json(&self, f: &mut String, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent + 1);
+        match self {
+            Json::Array(items) if !items.is_empty() => {
+                f.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    f.push_str(&pad);
+                    item.write_json(f, indent + 1)?;
+                    f.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                write!(f, "{}]", "  ".repeat(indent))
+            }
+            Json::Object(entries) if !entries.is_empty() => {
+                f.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    write!(f, "{pad}{}: ", quote(key))?;
+                    value.write_json(f, indent + 1)?;
+                    f.push_str(if index + 1 < entries.len() {
+                        ",\n"
+                    } else {
+                        "\n"
+                    });
+                }
+                write!(f, "{}}}", "  ".repeat(indent))
+            }
+            scalar => write!(f, "{}", scalar.scalar()),
+        }
+    }
+
+    /// Writes a non-empty array or object as a YAML block
+    fn write_yaml_block(&self, f: &mut String, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent);
+        match self {
+            Json::Array(items) => {
+                for item in items {
+                    if !item.is_yaml_block() {
+                        writeln!(f, "{pad}- {}", item.scalar())?;
+                        continue;
+                    }
+                    // The first line of the item goes on the same line as the dash
+                    let mut block = String::new();
+                    item.write_yaml_block(&mut block, indent + 1)?;
+                    write!(f, "{pad}- {}", &block[pad.len() + 2..])?;
+                }
+            }
+            Json::Object(entries) => {
+                for (key, value) in entries {
+                    let key = yaml_key(key);
+                    if value.is_yaml_block() {
+                        writeln!(f, "{pad}{key}:")?;
+                        value.write_yaml_block(f, indent + 1)?;
+                    } else {
+                        writeln!(f, "{pad}{key}: {}", value.scalar())?;
+                    }
+                }
+            }
+            _ => unreachable!("only arrays & objects are written as blocks"),
+        }
+        Ok(())
+    }
+
+    fn is_yaml_block(&self) -> bool {
+        match self {
+            Json::Array(items) => !items.is_empty(),
+            Json::Object(entries) => !entries.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Formats a scalar or empty collection.  These look the same in JSON & YAML.
+    fn scalar(&self) -> String {
+        match self {
+            Json::Bool(value) => value.to_string(),
+            Json::Int(value) => value.to_string(),
+            Json::String(value) => quote(value),
+            Json::Array(_) => "[]".to_string(),
+            Json::Object(_) => "{}".to_string(),
+        }
+    }
+}
+
+/// Quotes & escapes a string for JSON, which is also a valid YAML string
+fn quote(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Formats a YAML mapping key, only quoting it if it needs to be
+fn yaml_key(key: &str) -> String {
+    let plain = key
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        .unwrap_or(false)
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '-' | '.'))
+        // YAML 1.1 parsers read these as booleans or null in any case, e.g. `True` or `NO`
+        && !matches!(
+            key.to_ascii_lowercase().as_str(),
+            "true" | "false" | "null" | "~" | "y" | "n" | "yes" | "no" | "on" | "off"
+        );
+    match plain {
+        true => key.to_string(),
+        false => quote(key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Json {
+        Json::object([
+            ("name", Json::string("go-away")),
+            ("version", Json::Int(1)),
+            ("empty", Json::Array(vec![])),
+            (
+                "items",
+                Json::Array(vec![
+                    Json::object([("$ref", Json::string("#/a")), ("ok", Json::Bool(true))]),
+                    Json::Bool(false),
+                    Json::Array(vec![Json::Int(1), Json::Int(2)]),
+                ]),
+            ),
+            ("/paths/{id}", Json::object([("200", Json::Object(vec![]))])),
+        ])
+    }
+
+    #[test]
+    fn test_json() {
+        insta::assert_snapshot!(document().to_json(), @r###"
+        {
+          "name": "go-away",
+          "version": 1,
+          "empty": [],
+          "items": [
+            {
+              "$ref": "#/a",
+              "ok": true
+            },
+            false,
+            [
+              1,
+              2
+            ]
+          ],
+          "/paths/{id}": {
+            "200": {}
+          }
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_yaml() {
+        insta::assert_snapshot!(document().to_yaml(), @r###"
+        name: "go-away"
+        version: 1
+        empty: []
+        items:
+          - $ref: "#/a"
+            ok: true
+          - false
+          - - 1
+            - 2
+        "/paths/{id}":
+          "200": {}
+        "###);
+
+        let keys = ["True", "NULL", "~", "y", "N", "Yes", "OFF", "Only"];
+        let object = Json::object(keys.map(|key| (key, Json::Bool(true))));
+        insta::assert_snapshot!(object.to_yaml(), @r###"
+        "True": true
+        "NULL": true
+        "~": true
+        "y": true
+        "N": true
+        "Yes": true
+        "OFF": true
+        Only: true
+        "###);
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            Json::string("a \"quoted\"\nline\\").to_json(),
+            "\"a \\\"quoted\\\"\\nline\\\\\"\n"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! JSON Schema (draft 2020-12) documents
+
+use std::fmt;
+
+use super::{json::Json, schema::Schemas};
+use crate::registry::Type;
+
+pub use crate::types::*;
+
+/// An enum representing the possible top-level types as JSON schemas
+///
+/// This is mostly used via `registry_to_json_schema`, which outputs a single document
+/// with every type in `$defs`.  Displaying one of these outputs the schema for just that
+/// type, which refers to other types as `#/$defs/Name`.
+pub enum JsonSchemaType<'a> {
+    /// A struct variant
+    Struct(&'a Struct),
+    /// A new type variant
+    NewType(&'a NewType),
+    /// A type alias variant
+    Alias(&'a Alias),
+    /// A simple enum variant (does not contain data)
+    Enum(&'a Enum),
+    /// A union variant (enums with data)
+    Union(&'a Union),
+}
+
+/// Options for the generated JSON schema document.
+///
+/// Use with `registry_to_json_schema`.
+#[derive(Clone, Debug, Default)]
+pub struct JsonSchemaOptions {
+    id: Option<String>,
+    root: Option<String>,
+}
+
+impl JsonSchemaOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        JsonSchemaOptions::default()
+    }
+
+    /// Sets the `$id` of the document, which other schemas can use to refer to it
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Makes the document itself validate the type named `name`.
+    ///
+    /// Without this the document only contains `$defs`, and each type has to be referred
+    /// to with a JSON pointer such as `#/$defs/Name`.
+    pub fn with_root(mut self, name: impl Into<String>) -> Self {
+        self.root = Some(name.into());
+        self
+    }
+}
+
+const SCHEMAS: Schemas<'static> = Schemas {
+    ref_prefix: "#/$defs/",
+    discriminators: false,
+    type_names: &[],
+};
+
+impl JsonSchemaType<'_> {
+    fn name(&self) -> &str {
+        match self {
+            JsonSchemaType::Struct(details) => &details.name,
+            JsonSchemaType::NewType(details) => &details.name,
+            JsonSchemaType::Alias(details) => &details.name,
+            JsonSchemaType::Enum(details) => &details.name,
+            JsonSchemaType::Union(details) => &details.name,
+        }
+    }
+
+    fn schema(&self) -> Json {
+        match self {
+            JsonSchemaType::Struct(details) => SCHEMAS.struct_schema(details),
+            JsonSchemaType::NewType(details) => SCHEMAS.field_type(&details.inner),
+            JsonSchemaType::Alias(details) => SCHEMAS.field_type(&details.inner),
+            JsonSchemaType::Enum(details) => SCHEMAS.enum_schema(details),
+            JsonSchemaType::Union(details) => SCHEMAS.union_schema(details),
+        }
+    }
+}
+
+impl fmt::Display for JsonSchemaType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.schema().to_json())
+    }
+}
+
+/// Outputs a JSON schema document with each of `types` in `$defs`
+pub(crate) fn document<'a>(
+    types: impl Iterator<Item = &'a Type>,
+    options: &JsonSchemaOptions,
+) -> String {
+    let mut defs = types.map(JsonSchemaType::from).collect::<Vec<_>>();
+    defs.sort_by(|lhs, rhs| lhs.name().cmp(rhs.name()));
+
+    let mut document = Json::object([(
+        "$schema",
+        Json::string("https://json-schema.org/draft/2020-12/schema"),
+    )]);
+    if let Some(id) = &options.id {
+        document.insert("$id", Json::string(id));
+    }
+    if let Some(root) = &options.root {
+        document.insert(
+            "$ref",
+            Json::string(format!("{}{root}", SCHEMAS.ref_prefix)),
+        );
+    }
+    document.insert(
+        "$defs",
+        Json::object(defs.iter().map(|ty| (ty.name(), ty.schema()))),
+    );
+
+    document.to_json()
+}
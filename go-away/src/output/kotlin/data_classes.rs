@@ -1,6 +1,6 @@
 use crate::output::prelude::*;
 
-use super::{kserializer::KSerializer, to_camel_case};
+use super::{keywords::escape_identifier, kserializer::KSerializer, to_camel_case};
 
 pub struct DataClass<'a> {
     name: &'a str,
@@ -100,7 +100,8 @@ impl<'a> NewTypeClass<'a> {
 }
 
 fn serializer_name(name: &str) -> String {
-    format!("{name}Serializer")
+    // Escaped names are wrapped in backticks, which can't appear in the middle of a name
+    format!("{}Serializer", name.trim_matches('`'))
 }
 
 impl fmt::Display for NewTypeClass<'_> {
@@ -128,7 +129,7 @@ impl fmt::Display for NewTypeClass<'_> {
 impl<'a> From<&'a crate::types::Field> for Field<'a> {
     fn from(val: &'a crate::types::Field) -> Self {
         Field {
-            name: to_camel_case(&val.name),
+            name: escape_identifier(to_camel_case(&val.name)),
             ty: val.ty.kotlin_type(),
             serde_name: &val.serialized_name,
             default_str: val.ty.default_str(),
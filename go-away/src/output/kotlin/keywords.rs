@@ -0,0 +1,39 @@
+/// Kotlin's hard keywords, which can't be used as identifiers without escaping
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Escapes an identifier that clashes with a kotlin keyword by wrapping it in backticks
+pub fn escape_identifier(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) {
+        return format!("`{name}`");
+    }
+    name
+}
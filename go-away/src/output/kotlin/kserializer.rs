@@ -17,7 +17,7 @@ impl<'a> KSerializer<'a> {
             target_name,
             serialize_body: String::new(),
             deserialize_body: String::new(),
-            serializer_name: format!("{}Serializer", target_name),
+            serializer_name: format!("{}Serializer", target_name.trim_matches('`')),
             descriptor: String::new(),
             additional_members: String::new(),
         }
@@ -9,6 +9,7 @@ use super::go::FieldType;
 
 mod data_classes;
 mod enums;
+mod keywords;
 mod kserializer;
 mod structs;
 mod unions;
@@ -79,7 +80,7 @@ impl FieldType {
             }
             FieldType::Primitive(Primitive::String) => "String".to_string(),
             FieldType::Primitive(Primitive::Float) => "Double".to_string(),
-            FieldType::Primitive(Primitive::Int) => "Long".to_string(),
+            FieldType::Primitive(Primitive::Int(_)) => "Long".to_string(),
             FieldType::Primitive(Primitive::Bool) => "Boolean".to_string(),
             FieldType::Primitive(Primitive::Time) => {
                 // Also: is this a datetime or just a time.  Might need to expand the primitive support somewhat...
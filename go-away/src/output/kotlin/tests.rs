@@ -1,7 +1,9 @@
 use insta::assert_snapshot;
 
 use super::*;
-use crate::types::{EnumVariant, Field, Primitive};
+use crate::types::{
+    EnumVariant, Field, IntKind, Primitive, TypeRef, UnionRepresentation, UnionVariant,
+};
 
 #[test]
 fn test_primitive_structs() {
@@ -13,26 +15,41 @@ fn test_primitive_structs() {
                     name: "a_string".into(),
                     serialized_name: "a_string".into(),
                     ty: FieldType::Primitive(Primitive::String),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "an_int".into(),
                     serialized_name: "renamed_tho".into(),
-                    ty: FieldType::Primitive(Primitive::Int),
+                    ty: FieldType::Primitive(Primitive::Int(IntKind::I64)),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "a_bool".into(),
                     serialized_name: "also_renamed".into(),
                     ty: FieldType::Primitive(Primitive::Bool),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "a_float".into(),
                     serialized_name: "a_float".into(),
                     ty: FieldType::Primitive(Primitive::Float),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "optionalFloat".into(),
                     serialized_name: "a_float".into(),
                     ty: FieldType::Optional(Box::new(FieldType::Primitive(Primitive::Float))),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
             ],
         })
@@ -113,34 +130,50 @@ fn test_enum_output() {
     "###);
 }
 
-/* TODO:
-#[test]
-fn test_adjacently_tagged_union_output() {
-    assert_snapshot!(KotlinType::Union(&types::Union {
-        name: "MyUnion".into(),
-        representation: UnionRepresentation::AdjacentlyTagged {
-            tag: "type".into(),
-            content: "data".into(),
-        },
+fn status_union(representation: UnionRepresentation) -> types::Union {
+    types::Union {
+        name: "Status".into(),
+        representation,
         variants: vec![
             UnionVariant {
-                name: Some("VarOne".into()),
-                ty: FieldType::Named(TypeRef {
-                    name: "VarOne".into()
-                }),
-                serialized_name: "VAR_ONE".into(),
+                name: Some("Pending".into()),
+                ty: None,
+                serialized_name: "PENDING".into(),
             },
             UnionVariant {
-                name: Some("VarTwo".into()),
-                ty: FieldType::Named(TypeRef {
-                    name: "VarTwo".into()
-                }),
-                serialized_name: "VAR_TWO".into(),
-            }
-        ]
-    })
-    .to_string());
-} */
+                name: Some("Failed".into()),
+                ty: Some(FieldType::Named(TypeRef::new("Failure"))),
+                serialized_name: "FAILED".into(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_adjacently_tagged_union_output() {
+    assert_snapshot!(
+        KotlinType::Union(&status_union(UnionRepresentation::AdjacentlyTagged {
+            tag: "type".into(),
+            content: "data".into(),
+        }))
+        .to_string()
+    );
+}
+
+#[test]
+fn test_internally_tagged_union_output() {
+    assert_snapshot!(
+        KotlinType::Union(&status_union(UnionRepresentation::InternallyTagged {
+            tag: "type".into(),
+        }))
+        .to_string()
+    );
+}
+
+#[test]
+fn test_untagged_union_output() {
+    assert_snapshot!(KotlinType::Union(&status_union(UnionRepresentation::Untagged)).to_string());
+}
 
 #[test]
 fn test_list_types() {
@@ -155,7 +188,7 @@ fn test_map_types() {
     assert_snapshot!(
         FieldType::Map{
             key: Box::new(FieldType::Primitive(Primitive::String)),
-            value: Box::new(FieldType::Primitive(Primitive::Int))
+            value: Box::new(FieldType::Primitive(Primitive::Int(IntKind::I64)))
         }.kotlin_type(),
         @"Map<String, Long>"
     );
@@ -3,7 +3,7 @@ use crate::{
     types::{self, UnionRepresentation},
 };
 
-use super::{data_classes::NewTypeClass, to_camel_case};
+use super::{data_classes::NewTypeClass, keywords::escape_identifier, to_camel_case};
 
 pub struct Union<'a> {
     name: &'a str,
@@ -28,15 +28,27 @@ impl<'a> Union<'a> {
 
 struct Variant<'a> {
     name: String,
+    /// The kotlin type & serializer of the variants data, or None for unit variants
+    data: Option<VariantData>,
+    serde_name: &'a str,
+}
+
+struct VariantData {
     ty: String,
     inner_serializer: String,
-    serde_name: &'a str,
 }
 
 impl<'a> Variant<'a> {
-    fn newtype(&'a self, enum_class: &'a str) -> NewTypeClass<'a> {
-        NewTypeClass::new(&self.name, self.ty.clone(), self.inner_serializer.clone())
-            .with_inheritance(enum_class)
+    fn class(&'a self, enum_class: &'a str) -> String {
+        match &self.data {
+            Some(VariantData {
+                ty,
+                inner_serializer,
+            }) => NewTypeClass::new(&self.name, ty.clone(), inner_serializer.clone())
+                .with_inheritance(enum_class)
+                .to_string(),
+            None => format!("object {} : {enum_class}\n", self.name),
+        }
     }
 }
 
@@ -45,13 +57,15 @@ impl<'a> From<&'a types::UnionVariant> for Variant<'a> {
         Variant {
             // TODO: This should not be camel cased, but removing that change causes
             // null pointer exceptions :sob:
-            name: to_camel_case(
+            name: escape_identifier(to_camel_case(
                 val.name
                     .as_ref()
                     .expect("union variants to generally have names"),
-            ),
-            inner_serializer: val.ty.serializer(),
-            ty: val.ty.kotlin_type(),
+            )),
+            data: val.ty.as_ref().map(|ty| VariantData {
+                ty: ty.kotlin_type(),
+                inner_serializer: ty.serializer(),
+            }),
             serde_name: &val.serialized_name,
         }
     }
@@ -65,8 +79,8 @@ impl fmt::Display for Union<'_> {
         writeln!(f, "sealed interface {name} {{")?;
         writeln_for!(
             indented(f),
-            newtype in self.variants.iter().map(|v| v.newtype(name)),
-            "{newtype}"
+            class in self.variants.iter().map(|v| v.class(name)),
+            "{class}"
         );
         writeln!(f, "}}")?;
 
@@ -87,7 +101,6 @@ impl fmt::Display for Union<'_> {
                 });
                 serializer.descriptor(AdjacentlyTaggedDescriptor {
                     name: self.name,
-                    variants: &self.variants,
                     tag,
                     content,
                 });
@@ -105,7 +118,6 @@ impl fmt::Display for Union<'_> {
                 });
                 serializer.descriptor(InternallyTaggedDescriptor {
                     name: self.name,
-                    variants: &self.variants,
                     tag,
                 });
             }
@@ -123,7 +135,17 @@ impl fmt::Display for Union<'_> {
                     variants: &self.variants,
                 });
             }
-            UnionRepresentation::Untagged => todo!(),
+            UnionRepresentation::Untagged => {
+                serializer.serialize_body(UntaggedSerialize {
+                    name,
+                    variants: &self.variants,
+                });
+                serializer.deserialize_body(UntaggedDeserialize {
+                    name,
+                    variants: &self.variants,
+                });
+                serializer.descriptor("JsonElement.serializer().descriptor\n");
+            }
         }
         writeln!(f, "{serializer}")?;
         Ok(())
@@ -138,6 +160,35 @@ struct ExternallyTaggedSerialize<'a> {
 impl fmt::Display for ExternallyTaggedSerialize<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ExternallyTaggedSerialize { name, variants } = self;
+        if variants.iter().any(|v| v.data.is_none()) {
+            // Unit variants are serialized as a plain string, so each branch needs to
+            // decide for itself whether to open a structure
+            writeln!(f, "when(value) {{")?;
+            for (i, variant) in variants.iter().enumerate() {
+                let Variant {
+                    name: variant_name,
+                    serde_name,
+                    ..
+                } = variant;
+                match variant.data {
+                    Some(_) => writedoc!(
+                        indented(f),
+                        r#"
+                            is {name}.{variant_name} -> {{
+                                val composite = encoder.beginStructure(descriptor)
+                                composite.encodeSerializableElement(descriptor, {i}, {name}.{variant_name}.serializer(), value as {name}.{variant_name})
+                                composite.endStructure(descriptor)
+                            }}
+                        "#
+                    )?,
+                    None => writeln!(
+                        indented(f),
+                        r#"is {name}.{variant_name} -> encoder.encodeString("{serde_name}")"#
+                    )?,
+                }
+            }
+            return writeln!(f, "}}");
+        }
         writeln!(f, "val composite = encoder.beginStructure(descriptor)")?;
         writeln!(f, "when(value) {{")?;
         writedoc_for!(
@@ -161,6 +212,53 @@ struct ExternallyTaggedDeserialize<'a> {
 impl fmt::Display for ExternallyTaggedDeserialize<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ExternallyTaggedDeserialize { name, variants } = self;
+        if variants.iter().any(|v| v.data.is_none()) {
+            // We need to peek at the JSON to tell unit variants (which are plain strings)
+            // apart from the others
+            writedoc!(
+                f,
+                r#"
+                    val jsonDecoder = decoder as JsonDecoder
+                    val element = jsonDecoder.decodeJsonElement()
+                    if (element is JsonPrimitive) {{
+                        return when (element.content) {{
+                "#
+            )?;
+            for Variant {
+                name: variant_name,
+                serde_name,
+                ..
+            } in variants.iter().filter(|v| v.data.is_none())
+            {
+                writeln!(
+                    indented(&mut indented(f)),
+                    r#""{serde_name}" -> {name}.{variant_name}"#
+                )?;
+            }
+            writedoc!(
+                f,
+                r#"
+                            else -> error("Unexpected input")
+                        }}
+                    }}
+                    val (key, data) = element.jsonObject.entries.single()
+                    return when (key) {{
+                "#
+            )?;
+            for Variant {
+                name: variant_name,
+                serde_name,
+                ..
+            } in variants.iter().filter(|v| v.data.is_some())
+            {
+                writeln!(
+                    indented(f),
+                    r#""{serde_name}" -> jsonDecoder.json.decodeFromJsonElement({name}.{variant_name}.serializer(), data)"#
+                )?;
+            }
+            writeln!(indented(f), r#"else -> error("Unexpected input")"#)?;
+            return writeln!(f, "}}");
+        }
         writeln!(f, "val composite = decoder.beginStructure(descriptor)")?;
         writeln!(
             f,
@@ -189,16 +287,27 @@ impl fmt::Display for ExternallyTaggedDescriptor<'_> {
         let ExternallyTaggedDescriptor { name, variants } = self;
 
         writeln!(f, r#"buildClassSerialDescriptor("{name}") {{"#)?;
-        writeln_for!(
-            indented(f),
-            Variant { name: variant_name, serde_name, .. } in *variants,
-            r#"element<{name}.{variant_name}>("{serde_name}", isOptional = true)"#
-        );
+        for Variant {
+            name: variant_name,
+            serde_name,
+            data,
+        } in *variants
+        {
+            match data {
+                Some(_) => writeln!(
+                    indented(f),
+                    r#"element<{name}.{variant_name}>("{serde_name}", isOptional = true)"#
+                )?,
+                None => writeln!(
+                    indented(f),
+                    r#"element<String>("{serde_name}", isOptional = true)"#
+                )?,
+            }
+        }
         writeln!(f, "}};\n")
     }
 }
 
-#[allow(dead_code)]
 struct InternallyTaggedSerialize<'a> {
     name: &'a str,
     tag: &'a str,
@@ -206,12 +315,39 @@ struct InternallyTaggedSerialize<'a> {
 }
 
 impl fmt::Display for InternallyTaggedSerialize<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("Finish internally tagged enum support")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let InternallyTaggedSerialize {
+            name,
+            tag,
+            variants,
+        } = self;
+        // The tag goes alongside the fields of the variants data, so we build up
+        // the JSON for each variant by hand
+        writeln!(f, "val jsonEncoder = encoder as JsonEncoder")?;
+        writeln!(f, "val element = when (value) {{")?;
+        for Variant {
+            name: variant_name,
+            serde_name,
+            data,
+        } in variants.iter()
+        {
+            let tag_entry = format!(r#""{tag}" to JsonPrimitive("{serde_name}")"#);
+            match data {
+                Some(_) => writeln!(
+                    indented(f),
+                    "is {name}.{variant_name} -> JsonObject(mapOf({tag_entry}) + jsonEncoder.json.encodeToJsonElement({name}.{variant_name}.serializer(), value).jsonObject)"
+                )?,
+                None => writeln!(
+                    indented(f),
+                    "is {name}.{variant_name} -> JsonObject(mapOf({tag_entry}))"
+                )?,
+            }
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "jsonEncoder.encodeJsonElement(element)")
     }
 }
 
-#[allow(dead_code)]
 struct InternallyTaggedDeserialize<'a> {
     name: &'a str,
     tag: &'a str,
@@ -219,25 +355,54 @@ struct InternallyTaggedDeserialize<'a> {
 }
 
 impl fmt::Display for InternallyTaggedDeserialize<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("Finish internally tagged enum support")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let InternallyTaggedDeserialize {
+            name,
+            tag,
+            variants,
+        } = self;
+        writedoc!(
+            f,
+            r#"
+                val jsonDecoder = decoder as JsonDecoder
+                val element = jsonDecoder.decodeJsonElement().jsonObject
+                return when (element["{tag}"]?.jsonPrimitive?.content) {{
+            "#
+        )?;
+        for Variant {
+            name: variant_name,
+            serde_name,
+            data,
+        } in variants.iter()
+        {
+            match data {
+                // The variants data doesn't expect the tag, so it needs removing
+                Some(_) => writeln!(
+                    indented(f),
+                    r#""{serde_name}" -> jsonDecoder.json.decodeFromJsonElement({name}.{variant_name}.serializer(), JsonObject(element - "{tag}"))"#
+                )?,
+                None => writeln!(indented(f), r#""{serde_name}" -> {name}.{variant_name}"#)?,
+            }
+        }
+        writeln!(indented(f), r#"else -> error("Unexpected input")"#)?;
+        writeln!(f, "}}")
     }
 }
 
-#[allow(dead_code)]
 struct InternallyTaggedDescriptor<'a> {
     name: &'a str,
     tag: &'a str,
-    variants: &'a [Variant<'a>],
 }
 
 impl fmt::Display for InternallyTaggedDescriptor<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("Finish internally tagged enum support")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let InternallyTaggedDescriptor { name, tag } = self;
+        writeln!(f, r#"buildClassSerialDescriptor("{name}") {{"#)?;
+        writeln!(indented(f), r#"element<String>("{tag}")"#)?;
+        writeln!(f, "}};\n")
     }
 }
 
-#[allow(dead_code)]
 struct AdjacentlyTaggedSerialize<'a> {
     name: &'a str,
     tag: &'a str,
@@ -247,24 +412,38 @@ struct AdjacentlyTaggedSerialize<'a> {
 
 impl fmt::Display for AdjacentlyTaggedSerialize<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let AdjacentlyTaggedSerialize { name, variants, .. } = self;
-        writeln!(f, "val composite = encoder.beginStructure(descriptor)")?;
-        writeln!(f, "when(value) {{")?;
-        writedoc_for!(
-            indented(f),
-            Variant { name: variant_name, .. } in variants.iter(),
-            r#"
-                is {name}.{variant_name} ->
-                    composite.encodeSerializableElement(descriptor, 0, String.serializer(), "{name}")
-                    composite.encodeSerializableElement(descriptor, 1, {name}.{variant_name}.serializer(), value as {name}.{variant_name})
-            "#
-        );
+        let AdjacentlyTaggedSerialize {
+            name,
+            tag,
+            content,
+            variants,
+        } = self;
+        writeln!(f, "val jsonEncoder = encoder as JsonEncoder")?;
+        writeln!(f, "val element = when (value) {{")?;
+        for Variant {
+            name: variant_name,
+            serde_name,
+            data,
+        } in variants.iter()
+        {
+            let tag_entry = format!(r#""{tag}" to JsonPrimitive("{serde_name}")"#);
+            match data {
+                Some(_) => writeln!(
+                    indented(f),
+                    r#"is {name}.{variant_name} -> JsonObject(mapOf({tag_entry}, "{content}" to jsonEncoder.json.encodeToJsonElement({name}.{variant_name}.serializer(), value)))"#
+                )?,
+                // Unit variants only have a tag
+                None => writeln!(
+                    indented(f),
+                    "is {name}.{variant_name} -> JsonObject(mapOf({tag_entry}))"
+                )?,
+            }
+        }
         writeln!(f, "}}")?;
-        writeln!(f, "composite.endStructure(descriptor)")
+        writeln!(f, "jsonEncoder.encodeJsonElement(element)")
     }
 }
 
-#[allow(dead_code)]
 struct AdjacentlyTaggedDeserialize<'a> {
     name: &'a str,
     tag: &'a str,
@@ -273,21 +452,114 @@ struct AdjacentlyTaggedDeserialize<'a> {
 }
 
 impl fmt::Display for AdjacentlyTaggedDeserialize<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("impl adjacent tagging for kotlin")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let AdjacentlyTaggedDeserialize {
+            name,
+            tag,
+            content,
+            variants,
+        } = self;
+        writedoc!(
+            f,
+            r#"
+                val jsonDecoder = decoder as JsonDecoder
+                val element = jsonDecoder.decodeJsonElement().jsonObject
+                return when (element["{tag}"]?.jsonPrimitive?.content) {{
+            "#
+        )?;
+        for Variant {
+            name: variant_name,
+            serde_name,
+            data,
+        } in variants.iter()
+        {
+            match data {
+                Some(_) => writeln!(
+                    indented(f),
+                    r#""{serde_name}" -> jsonDecoder.json.decodeFromJsonElement({name}.{variant_name}.serializer(), element.getValue("{content}"))"#
+                )?,
+                None => writeln!(indented(f), r#""{serde_name}" -> {name}.{variant_name}"#)?,
+            }
+        }
+        writeln!(indented(f), r#"else -> error("Unexpected input")"#)?;
+        writeln!(f, "}}")
     }
 }
 
-#[allow(dead_code)]
 struct AdjacentlyTaggedDescriptor<'a> {
     name: &'a str,
     tag: &'a str,
     content: &'a str,
-    variants: &'a [Variant<'a>],
 }
 
 impl fmt::Display for AdjacentlyTaggedDescriptor<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("impl adjacent tagging for kotlin")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let AdjacentlyTaggedDescriptor { name, tag, content } = self;
+        writeln!(f, r#"buildClassSerialDescriptor("{name}") {{"#)?;
+        writeln!(indented(f), r#"element<String>("{tag}")"#)?;
+        writeln!(
+            indented(f),
+            r#"element<JsonElement>("{content}", isOptional = true)"#
+        )?;
+        writeln!(f, "}};\n")
+    }
+}
+
+struct UntaggedSerialize<'a> {
+    name: &'a str,
+    variants: &'a [Variant<'a>],
+}
+
+impl fmt::Display for UntaggedSerialize<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let UntaggedSerialize { name, variants } = self;
+        writeln!(f, "val jsonEncoder = encoder as JsonEncoder")?;
+        writeln!(f, "val element = when (value) {{")?;
+        for Variant {
+            name: variant_name,
+            data,
+            ..
+        } in variants.iter()
+        {
+            match data {
+                Some(_) => writeln!(
+                    indented(f),
+                    "is {name}.{variant_name} -> jsonEncoder.json.encodeToJsonElement({name}.{variant_name}.serializer(), value)"
+                )?,
+                // serde writes untagged unit variants as null
+                None => writeln!(indented(f), "is {name}.{variant_name} -> JsonNull")?,
+            }
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "jsonEncoder.encodeJsonElement(element)")
+    }
+}
+
+struct UntaggedDeserialize<'a> {
+    name: &'a str,
+    variants: &'a [Variant<'a>],
+}
+
+impl fmt::Display for UntaggedDeserialize<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let UntaggedDeserialize { name, variants } = self;
+        writeln!(f, "val jsonDecoder = decoder as JsonDecoder")?;
+        writeln!(f, "val element = jsonDecoder.decodeJsonElement()")?;
+        // Like serde, we try each variant in order & use the first that matches.
+        // null matches the first unit variant.
+        if let Some(unit) = variants.iter().find(|v| v.data.is_none()) {
+            writeln!(f, "if (element is JsonNull) {{")?;
+            writeln!(indented(f), "return {name}.{}", unit.name)?;
+            writeln!(f, "}}")?;
+        }
+        writedoc_for!(
+            &mut *f,
+            Variant { name: variant_name, .. } in variants.iter().filter(|v| v.data.is_some()),
+            r#"
+                runCatching {{ jsonDecoder.json.decodeFromJsonElement({name}.{variant_name}.serializer(), element) }}
+                    .onSuccess {{ return it }}
+            "#
+        );
+        writeln!(f, r#"error("Unexpected input")"#)
     }
 }
@@ -1,15 +1,77 @@
 pub mod go;
+pub mod json_schema;
 pub mod kotlin;
+pub mod openapi;
 pub mod swift;
 pub mod typescript;
+pub mod zod;
 
-pub use go::GoType;
+pub use go::{GoClient, GoClientOptions, GoOmitEmpty, GoOptions, GoType, GoUnionStyle};
+pub use json_schema::{JsonSchemaOptions, JsonSchemaType};
 pub use kotlin::KotlinType;
+pub use openapi::{OpenApiFormat, OpenApiOptions};
 pub use swift::SwiftType;
-pub use typescript::TypeScriptType;
+pub use typescript::{
+    TypeScriptClient, TypeScriptClientOptions, TypeScriptEnumStyle, TypeScriptOptions,
+    TypeScriptStructStyle, TypeScriptType, TypeScriptWideIntegers,
+};
+pub use zod::ZodSchema;
 
+mod json;
+mod schema;
 mod tabify;
 
+use std::fmt;
+
+/// An output format that can be customised with some options.
+///
+/// Formats implementing this can be used with `registry_to_output_with_options`.
+pub trait ConfigurableFormat {
+    /// The options for this format
+    type Options;
+
+    /// Writes out this type, customised by `options`
+    fn fmt_with_options(&self, options: &Self::Options, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Writes out anything that's shared between all of `types`, ahead of the types themselves.
+    ///
+    /// By default this writes nothing.
+    fn fmt_prelude(
+        _types: &[Self],
+        _options: &Self::Options,
+        _f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+}
+
+/// Displays the prelude for a set of types using some options
+pub(crate) struct Prelude<'a, Format: ConfigurableFormat>(
+    pub &'a [Format],
+    pub &'a Format::Options,
+);
+
+impl<Format: ConfigurableFormat> fmt::Display for Prelude<'_, Format> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Format::fmt_prelude(self.0, self.1, f)
+    }
+}
+
+/// Displays a format using some options
+pub(crate) struct WithOptions<'a, Format: ConfigurableFormat>(
+    pub &'a Format,
+    pub &'a Format::Options,
+);
+
+impl<Format: ConfigurableFormat> fmt::Display for WithOptions<'_, Format> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_with_options(self.1, f)
+    }
+}
+
 mod prelude {
     pub use std::fmt::{self, Write};
 
@@ -0,0 +1,208 @@
+//! OpenAPI 3.1 documents
+
+use super::{json::Json, schema::Schemas};
+use crate::registry::Type;
+use crate::types::{FieldType, Operation};
+
+/// Options for the generated OpenAPI document.
+///
+/// Use with `registry_to_openapi`.
+#[derive(Clone, Debug)]
+pub struct OpenApiOptions {
+    title: String,
+    version: String,
+    format: OpenApiFormat,
+}
+
+/// The file format of an OpenAPI document
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OpenApiFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// YAML
+    Yaml,
+}
+
+impl OpenApiOptions {
+    /// Creates the options for a document describing version `version` of the API
+    /// named `title`
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        OpenApiOptions {
+            title: title.into(),
+            version: version.into(),
+            format: OpenApiFormat::default(),
+        }
+    }
+
+    /// Sets whether the document is output as JSON or YAML.
+    ///
+    /// Defaults to `OpenApiFormat::Json`.
+    pub fn with_format(mut self, format: OpenApiFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+const SCHEMAS: Schemas<'static> = Schemas {
+    ref_prefix: "#/components/schemas/",
+    discriminators: true,
+    type_names: &[],
+};
+
+/// Outputs an OpenAPI document with a schema for each of `types` and a path for each
+/// of `operations`.
+pub(crate) fn document<'a>(
+    types: impl Iterator<Item = &'a Type>,
+    operations: &[Operation],
+    options: &OpenApiOptions,
+) -> String {
+    let types = types.collect::<Vec<_>>();
+    let type_names = types.iter().map(|ty| ty.name()).collect::<Vec<_>>();
+    let builder = Schemas {
+        type_names: &type_names,
+        ..SCHEMAS
+    };
+
+    let mut schemas = vec![];
+    for ty in types {
+        match ty {
+            Type::Struct(details) => {
+                schemas.push((details.name.clone(), builder.struct_schema(details)))
+            }
+            Type::Enum(details) => {
+                schemas.push((details.name.clone(), builder.enum_schema(details)))
+            }
+            Type::Union(details) => {
+                schemas.push((details.name.clone(), builder.union_schema(details)));
+                schemas.extend(builder.variant_components(details));
+            }
+            Type::NewType(details) => {
+                schemas.push((details.name.clone(), builder.field_type(&details.inner)))
+            }
+            Type::Alias(details) => {
+                schemas.push((details.name.clone(), builder.field_type(&details.inner)))
+            }
+        }
+    }
+    schemas.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    let mut document = Json::object([
+        ("openapi", Json::string("3.1.0")),
+        (
+            "info",
+            Json::object([
+                ("title", Json::string(&options.title)),
+                ("version", Json::string(&options.version)),
+            ]),
+        ),
+    ]);
+    if !operations.is_empty() {
+        document.insert("paths", paths(operations));
+    }
+    document.insert(
+        "components",
+        Json::object([("schemas", Json::object(schemas))]),
+    );
+
+    match options.format {
+        OpenApiFormat::Json => document.to_json(),
+        OpenApiFormat::Yaml => document.to_yaml(),
+    }
+}
+
+/// The paths object, which groups operations by their path
+fn paths(operations: &[Operation]) -> Json {
+    let mut paths: Vec<(String, Vec<(String, Json)>)> = vec![];
+    for operation in operations {
+        let method = (
+            operation.method.as_str().to_lowercase(),
+            operation_object(operation),
+        );
+        match paths.iter_mut().find(|(path, _)| *path == operation.path) {
+            Some((_, methods)) => methods.push(method),
+            None => paths.push((operation.path.clone(), vec![method])),
+        }
+    }
+
+    Json::object(
+        paths
+            .into_iter()
+            .map(|(path, methods)| (path, Json::Object(methods))),
+    )
+}
+
+fn operation_object(operation: &Operation) -> Json {
+    let mut object = Json::object([("operationId", Json::string(&operation.name))]);
+
+    let mut parameters = operation
+        .path_params
+        .iter()
+        .map(|param| {
+            Json::object([
+                ("name", Json::string(&param.name)),
+                ("in", Json::string("path")),
+                ("required", Json::Bool(true)),
+                ("schema", SCHEMAS.field_type(&param.ty)),
+            ])
+        })
+        .collect::<Vec<_>>();
+    if let Some(query) = &operation.query {
+        // A form style object parameter puts each of its properties into the query string
+        parameters.push(Json::object([
+            ("name", Json::string("query")),
+            ("in", Json::string("query")),
+            ("style", Json::string("form")),
+            ("explode", Json::Bool(true)),
+            ("schema", SCHEMAS.field_type(query)),
+        ]));
+    }
+    if !parameters.is_empty() {
+        object.insert("parameters", Json::Array(parameters));
+    }
+
+    if let Some(request) = &operation.request {
+        object.insert(
+            "requestBody",
+            Json::object([
+                ("required", Json::Bool(true)),
+                ("content", json_content(request)),
+            ]),
+        );
+    }
+
+    let mut responses = vec![];
+    match &operation.response {
+        Some(response) => responses.push((
+            "200",
+            Json::object([
+                ("description", Json::string("Success")),
+                ("content", json_content(response)),
+            ]),
+        )),
+        None => responses.push((
+            "204",
+            Json::object([("description", Json::string("No Content"))]),
+        )),
+    }
+    if let Some(error) = &operation.error {
+        responses.push((
+            "default",
+            Json::object([
+                ("description", Json::string("Error")),
+                ("content", json_content(error)),
+            ]),
+        ));
+    }
+    object.insert("responses", Json::object(responses));
+
+    object
+}
+
+/// The `content` of a JSON request or response body
+fn json_content(ty: &FieldType) -> Json {
+    Json::object([(
+        "application/json",
+        Json::object([("schema", SCHEMAS.field_type(ty))]),
+    )])
+}
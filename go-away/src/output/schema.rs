@@ -0,0 +1,274 @@
+//! JSON schemas (draft 2020-12) for our types, shared by the formats built on JSON schema.
+
+use super::json::Json;
+use crate::types::{
+    Enum, FieldType, IntKind, Primitive, Struct, Union, UnionRepresentation, UnionVariant,
+};
+
+/// Builds JSON schemas for types
+pub struct Schemas<'a> {
+    /// The prefix for references to named types, e.g. `#/$defs/`
+    pub ref_prefix: &'a str,
+    /// Whether tagged unions get an OpenAPI `discriminator`
+    pub discriminators: bool,
+    /// The names of every type being output, which the schemas for variants of unions with a
+    /// discriminator mustn't clash with
+    pub type_names: &'a [&'a str],
+}
+
+impl Schemas<'_> {
+    pub fn field_type(&self, ty: &FieldType) -> Json {
+        match ty {
+            FieldType::Named(type_ref) => Json::object([(
+                "$ref",
+                Json::string(format!("{}{}", self.ref_prefix, type_ref.name())),
+            )]),
+            FieldType::Optional(inner) => Json::object([(
+                "anyOf",
+                Json::Array(vec![self.field_type(inner), type_schema("null")]),
+            )]),
+            FieldType::List(inner) => Json::object([
+                ("type", Json::string("array")),
+                ("items", self.field_type(inner)),
+            ]),
+            FieldType::Map { key, value } => {
+                let mut schema = Json::object([
+                    ("type", Json::string("object")),
+                    ("additionalProperties", self.field_type(value)),
+                ]);
+                // Keys are always strings in JSON, but named keys are usually enums which
+                // restrict what those strings can be
+                if let FieldType::Named(_) = key.as_ref() {
+                    schema.insert("propertyNames", self.field_type(key));
+                }
+                schema
+            }
+            FieldType::Primitive(Primitive::String) => type_schema("string"),
+            FieldType::Primitive(Primitive::Float) => type_schema("number"),
+            FieldType::Primitive(Primitive::Int(kind)) => int_schema(*kind),
+            FieldType::Primitive(Primitive::Bool) => type_schema("boolean"),
+            FieldType::Primitive(Primitive::Time) => Json::object([
+                ("type", Json::string("string")),
+                ("format", Json::string("date-time")),
+            ]),
+        }
+    }
+
+    pub fn struct_schema(&self, details: &Struct) -> Json {
+        let properties = details
+            .fields
+            .iter()
+            .map(|field| (field.serialized_name.clone(), self.field_type(&field.ty)))
+            .collect::<Vec<_>>();
+        // Fields with defaults & options can be missing when deserializing and skipped
+        // fields can be missing when serializing, everything else is always present
+        let required = details
+            .fields
+            .iter()
+            .filter(|field| {
+                !field.has_default
+                    && field.skip_serializing_if.is_none()
+                    && !matches!(field.ty, FieldType::Optional(_))
+            })
+            .map(|field| Json::string(&field.serialized_name))
+            .collect::<Vec<_>>();
+
+        object_schema(properties, required)
+    }
+
+    pub fn enum_schema(&self, details: &Enum) -> Json {
+        Json::object([
+            ("type", Json::string("string")),
+            (
+                "enum",
+                Json::Array(
+                    details
+                        .variants
+                        .iter()
+                        .map(|variant| Json::string(&variant.serialized_name))
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+
+    pub fn union_schema(&self, details: &Union) -> Json {
+        let Some(tag) = self.discriminator(details) else {
+            return Json::object([("oneOf", Json::Array(self.variant_schemas(details)))]);
+        };
+
+        // A discriminator's mapping can only refer to schemas by reference, so each variant
+        // is a separate schema - see `variant_components`
+        let refs = details
+            .variants
+            .iter()
+            .map(|variant| {
+                let reference = format!(
+                    "{}{}",
+                    self.ref_prefix,
+                    self.variant_component_name(details, variant)
+                );
+                (variant.serialized_name.clone(), reference)
+            })
+            .collect::<Vec<_>>();
+        Json::object([
+            (
+                "oneOf",
+                Json::Array(
+                    refs.iter()
+                        .map(|(_, reference)| Json::object([("$ref", Json::string(reference))]))
+                        .collect(),
+                ),
+            ),
+            (
+                "discriminator",
+                Json::object([
+                    ("propertyName", Json::string(tag)),
+                    (
+                        "mapping",
+                        Json::object(
+                            refs.into_iter()
+                                .map(|(value, reference)| (value, Json::string(reference))),
+                        ),
+                    ),
+                ]),
+            ),
+        ])
+    }
+
+    /// The schemas for each variant of a union with a discriminator, which need to be
+    /// output alongside the union itself - see `variant_component_name`
+    pub fn variant_components(&self, details: &Union) -> Vec<(String, Json)> {
+        if self.discriminator(details).is_none() {
+            return vec![];
+        }
+        details
+            .variants
+            .iter()
+            .map(|variant| self.variant_component_name(details, variant))
+            .zip(self.variant_schemas(details))
+            .collect()
+    }
+
+    /// The name of the schema for `variant` of a union with a discriminator.
+    ///
+    /// This is `<Union><Variant>`, with `Variant` added on the end until it doesn't clash
+    /// with any of the types, e.g. `PaymentCardVariant` when `Payment::Card` holds a
+    /// `PaymentCard`.
+    fn variant_component_name(&self, details: &Union, variant: &UnionVariant) -> String {
+        let variant_name = variant.name.as_ref().unwrap_or(&variant.serialized_name);
+        let mut name = format!("{}{variant_name}", details.name);
+        while self.type_names.contains(&name.as_str()) {
+            name.push_str("Variant");
+        }
+        name
+    }
+
+    /// The property that tells the variants of a union apart, if it gets a `discriminator`
+    fn discriminator<'a>(&self, details: &'a Union) -> Option<&'a str> {
+        match &details.representation {
+            UnionRepresentation::InternallyTagged { tag }
+            | UnionRepresentation::AdjacentlyTagged { tag, .. }
+                if self.discriminators =>
+            {
+                Some(tag)
+            }
+            _ => None,
+        }
+    }
+
+    fn variant_schemas(&self, details: &Union) -> Vec<Json> {
+        let variants = details.variants.iter();
+        match &details.representation {
+            UnionRepresentation::ExternallyTagged => variants
+                .map(|variant| match &variant.ty {
+                    None => const_schema(&variant.serialized_name),
+                    Some(ty) => {
+                        let mut schema = object_schema(
+                            vec![(variant.serialized_name.clone(), self.field_type(ty))],
+                            vec![Json::string(&variant.serialized_name)],
+                        );
+                        schema.insert("additionalProperties", Json::Bool(false));
+                        schema
+                    }
+                })
+                .collect(),
+            UnionRepresentation::InternallyTagged { tag } => variants
+                .map(|variant| {
+                    let tag_schema = tag_schema(tag, variant);
+                    match &variant.ty {
+                        None => tag_schema,
+                        Some(ty) => Json::object([(
+                            "allOf",
+                            Json::Array(vec![self.field_type(ty), tag_schema]),
+                        )]),
+                    }
+                })
+                .collect(),
+            UnionRepresentation::AdjacentlyTagged { tag, content } => variants
+                .map(|variant| {
+                    let mut properties =
+                        vec![(tag.clone(), const_schema(&variant.serialized_name))];
+                    let mut required = vec![Json::string(tag)];
+                    if let Some(ty) = &variant.ty {
+                        properties.push((content.clone(), self.field_type(ty)));
+                        required.push(Json::string(content));
+                    }
+                    object_schema(properties, required)
+                })
+                .collect(),
+            UnionRepresentation::Untagged => variants
+                .map(|variant| match &variant.ty {
+                    // Untagged unit variants are serialized as null
+                    None => type_schema("null"),
+                    Some(ty) => self.field_type(ty),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn type_schema(ty: &str) -> Json {
+    Json::object([("type", Json::string(ty))])
+}
+
+fn const_schema(value: &str) -> Json {
+    Json::object([("const", Json::string(value))])
+}
+
+fn object_schema(properties: Vec<(String, Json)>, required: Vec<Json>) -> Json {
+    let mut schema = Json::object([
+        ("type", Json::string("object")),
+        ("properties", Json::Object(properties)),
+    ]);
+    if !required.is_empty() {
+        schema.insert("required", Json::Array(required));
+    }
+    schema
+}
+
+/// An object with just the tag of a variant, for internally tagged unions
+fn tag_schema(tag: &str, variant: &UnionVariant) -> Json {
+    object_schema(
+        vec![(tag.to_string(), const_schema(&variant.serialized_name))],
+        vec![Json::string(tag)],
+    )
+}
+
+fn int_schema(kind: IntKind) -> Json {
+    let mut schema = type_schema("integer");
+    // The formats are signed, so unsigned integers need the next size up
+    let signed_bits = match kind.is_signed() {
+        true => kind.bits(),
+        false => kind.bits() + 1,
+    };
+    if signed_bits <= 32 {
+        schema.insert("format", Json::string("int32"));
+    } else if signed_bits <= 64 {
+        schema.insert("format", Json::string("int64"));
+    }
+    if !kind.is_signed() {
+        schema.insert("minimum", Json::Int(0));
+    }
+    schema
+}
@@ -3,7 +3,7 @@ use crate::{
     types::{self},
 };
 
-use super::{to_camel_case, CodingKey};
+use super::{keywords::escape_identifier, to_camel_case, CodingKey};
 
 pub struct Enum<'a> {
     name: &'a str,
@@ -32,7 +32,7 @@ struct Variant<'a> {
 impl<'a> From<&'a types::EnumVariant> for Variant<'a> {
     fn from(val: &'a types::EnumVariant) -> Self {
         Variant {
-            name: to_camel_case(&val.name),
+            name: escape_identifier(to_camel_case(&val.name)),
             serde_name: &val.serialized_name,
         }
     }
@@ -0,0 +1,67 @@
+/// Swift keywords that need escaping when used as identifiers
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "Type",
+    "as",
+    "associatedtype",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "precedencegroup",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Escapes an identifier that clashes with a swift keyword by wrapping it in backticks
+pub fn escape_identifier(name: String) -> String {
+    if KEYWORDS.contains(&name.as_str()) {
+        return format!("`{name}`");
+    }
+    name
+}
@@ -1,6 +1,6 @@
 use crate::{
     output::{prelude::*, swift::enums::Enum},
-    types::{self, Alias, Field, NewType, Struct},
+    types::{self, Alias, NewType, Struct},
 };
 
 use self::{
@@ -13,6 +13,7 @@ use super::go::FieldType;
 
 mod coding_keys;
 mod enums;
+mod keywords;
 mod structs;
 mod unions;
 
@@ -70,20 +71,6 @@ impl fmt::Display for SwiftType<'_> {
     }
 }
 
-pub struct SwiftField<'a>(&'a Field);
-
-impl fmt::Display for SwiftField<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let details = self.0;
-        write!(
-            f,
-            r#"public var {}: {}"#,
-            to_camel_case(&details.name),
-            details.ty.swift_type(),
-        )
-    }
-}
-
 impl FieldType {
     fn swift_type(&self) -> String {
         use crate::types::Primitive;
@@ -97,7 +84,7 @@ impl FieldType {
             }
             FieldType::Primitive(Primitive::String) => "String".to_string(),
             FieldType::Primitive(Primitive::Float) => "Double".to_string(),
-            FieldType::Primitive(Primitive::Int) => "Int64".to_string(),
+            FieldType::Primitive(Primitive::Int(_)) => "Int64".to_string(),
             FieldType::Primitive(Primitive::Bool) => "Bool".to_string(),
             FieldType::Primitive(Primitive::Time) => {
                 // Also: is this a datetime or just a time.  Might need to expand the primitive support somewhat...
@@ -105,6 +92,18 @@ impl FieldType {
             }
         }
     }
+
+    /// Whether this type refers to a recursive type anywhere within it
+    fn contains_recursive_type(&self) -> bool {
+        match self {
+            FieldType::Named(type_ref) => type_ref.is_recursive(),
+            FieldType::Optional(inner) | FieldType::List(inner) => inner.contains_recursive_type(),
+            FieldType::Map { key, value } => {
+                key.contains_recursive_type() || value.contains_recursive_type()
+            }
+            FieldType::Primitive(_) => false,
+        }
+    }
 }
 
 fn to_camel_case(s: &str) -> String {
@@ -1,9 +1,9 @@
 use std::fmt::{self, Write};
 
 use indenter::indented;
-use indoc::formatdoc;
+use indoc::{formatdoc, writedoc};
 
-use super::{codable::Codable, to_camel_case, CodingKey, CodingKeys};
+use super::{codable::Codable, keywords::escape_identifier, to_camel_case, CodingKey, CodingKeys};
 use crate::types::{self, FieldType};
 
 pub struct SwiftStruct<'a> {
@@ -28,6 +28,7 @@ impl<'a> SwiftStruct<'a> {
                 name: "value".to_string(),
                 ty: ty.swift_type(),
                 serde_name: "",
+                indirection: None,
             }],
             newtype: true,
         }
@@ -43,14 +44,35 @@ struct SwiftField<'a> {
     name: String,
     ty: String,
     serde_name: &'a str,
+    indirection: Option<Indirection>,
+}
+
+/// How a field that refers to its own struct is stored
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Indirection {
+    Required,
+    Optional,
 }
 
 impl<'a> From<&'a types::Field> for SwiftField<'a> {
     fn from(val: &'a types::Field) -> Self {
         SwiftField {
-            name: to_camel_case(&val.name),
+            name: escape_identifier(to_camel_case(&val.name)),
             ty: val.ty.swift_type(),
             serde_name: &val.serialized_name,
+            indirection: Indirection::of(&val.ty),
+        }
+    }
+}
+
+impl Indirection {
+    /// Swift structs can't contain themselves by value, so fields holding a recursive type
+    /// directly (rather than inside an array or dictionary) need an `Indirect` wrapper
+    fn of(ty: &FieldType) -> Option<Indirection> {
+        match ty {
+            FieldType::Named(type_ref) if type_ref.is_recursive() => Some(Indirection::Required),
+            FieldType::Optional(inner) => Indirection::of(inner).map(|_| Indirection::Optional),
+            _ => None,
         }
     }
 }
@@ -66,8 +88,19 @@ impl fmt::Display for SwiftStruct<'_> {
         writeln!(f, "public struct {name}: {impls} {{")?;
         {
             let f = &mut indented(f);
-            for SwiftField { name, ty, .. } in &self.fields {
-                writeln!(f, "public var {name}: {ty}")?;
+            for SwiftField {
+                name,
+                ty,
+                indirection,
+                ..
+            } in &self.fields
+            {
+                let wrapper = if indirection.is_some() {
+                    "@Indirect "
+                } else {
+                    ""
+                };
+                writeln!(f, "{wrapper}public var {name}: {ty}")?;
             }
             writeln!(f, "\npublic init(")?;
 
@@ -85,9 +118,33 @@ impl fmt::Display for SwiftStruct<'_> {
                 writeln!(indented(f), "self.{name} = {name}")?;
             }
             writeln!(f, "}}")?;
+
+            if self.fields.iter().any(|field| field.indirection.is_some()) {
+                writeln!(f, "\n{INDIRECT}")?;
+            }
         }
         writeln!(f, "}}\n")?;
 
+        // Property wrappers are always decoded with `decode`, which fails if the key is
+        // missing, so optional ones need an overload that uses `decodeIfPresent`
+        let optional_indirection = self
+            .fields
+            .iter()
+            .any(|field| field.indirection == Some(Indirection::Optional));
+        if optional_indirection {
+            writedoc!(
+                f,
+                "
+                    extension KeyedDecodingContainer {{
+                        func decode<Value: Hashable & Codable>(_ type: {name}.Indirect<Value?>.Type, forKey key: Key) throws -> {name}.Indirect<Value?> {{
+                            try decodeIfPresent(type, forKey: key) ?? {name}.Indirect(wrappedValue: nil)
+                        }}
+                    }}
+
+                "
+            )?;
+        }
+
         if !self.newtype {
             let coding_keys = CodingKeys::new().with_fields(&self.fields);
             writeln!(f, "extension {name} {{")?;
@@ -121,6 +178,38 @@ impl fmt::Display for SwiftStruct<'_> {
     }
 }
 
+/// A property wrapper that stores a value behind a reference, for fields that contain the
+/// struct they're in.  It's encoded & decoded as the value it wraps.
+const INDIRECT: &str = "\
+@propertyWrapper
+public indirect enum Indirect<Value: Hashable & Codable>: Hashable, Codable {
+    case wrapped(Value)
+
+    public init(wrappedValue: Value) {
+        self = .wrapped(wrappedValue)
+    }
+
+    public var wrappedValue: Value {
+        get {
+            switch self {
+            case .wrapped(let value):
+                return value
+            }
+        }
+        set {
+            self = .wrapped(newValue)
+        }
+    }
+
+    public init(from decoder: Decoder) throws {
+        self = .wrapped(try Value(from: decoder))
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        try wrappedValue.encode(to: encoder)
+    }
+}";
+
 impl<'a> From<&'a SwiftField<'a>> for CodingKey<'a> {
     fn from(field: &'a SwiftField<'a>) -> Self {
         CodingKey {
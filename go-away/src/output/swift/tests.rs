@@ -1,7 +1,9 @@
 use insta::assert_snapshot;
 
 use super::*;
-use crate::types::{EnumVariant, Primitive, TypeRef, UnionRepresentation, UnionVariant};
+use crate::types::{
+    EnumVariant, Field, IntKind, Primitive, TypeRef, UnionRepresentation, UnionVariant,
+};
 
 #[test]
 fn test_primitive_structs() {
@@ -13,21 +15,33 @@ fn test_primitive_structs() {
                     name: "a_string".into(),
                     serialized_name: "a_string".into(),
                     ty: FieldType::Primitive(Primitive::String),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "an_int".into(),
                     serialized_name: "renamed_tho".into(),
-                    ty: FieldType::Primitive(Primitive::Int),
+                    ty: FieldType::Primitive(Primitive::Int(IntKind::I64)),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "a_bool".into(),
                     serialized_name: "also_renamed".into(),
                     ty: FieldType::Primitive(Primitive::Bool),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
                 Field {
                     name: "a_float".into(),
                     serialized_name: "a_float".into(),
                     ty: FieldType::Primitive(Primitive::Float),
+                    go_tags: vec![],
+                    has_default: false,
+                    skip_serializing_if: None,
                 },
             ],
         })
@@ -138,16 +152,12 @@ fn test_adjacently_tagged_union_output() {
         variants: vec![
             UnionVariant {
                 name: Some("VarOne".into()),
-                ty: FieldType::Named(TypeRef {
-                    name: "VarOne".into()
-                }),
+                ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                 serialized_name: "VAR_ONE".into(),
             },
             UnionVariant {
                 name: Some("VarTwo".into()),
-                ty: FieldType::Named(TypeRef {
-                    name: "VarTwo".into()
-                }),
+                ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                 serialized_name: "VAR_TWO".into(),
             }
         ]
@@ -168,7 +178,7 @@ fn test_map_types() {
     assert_snapshot!(
         FieldType::Map{
             key: Box::new(FieldType::Primitive(Primitive::String)),
-            value: Box::new(FieldType::Primitive(Primitive::Int))
+            value: Box::new(FieldType::Primitive(Primitive::Int(IntKind::I64)))
         }.swift_type(),
         @"[String: Int64]"
     );
@@ -3,7 +3,7 @@ use crate::{
     types::{self, UnionRepresentation},
 };
 
-use super::{to_camel_case, CodingKey, CodingKeys};
+use super::{keywords::escape_identifier, to_camel_case, CodingKey, CodingKeys};
 
 pub struct Union<'a> {
     name: &'a str,
@@ -28,20 +28,27 @@ impl<'a> Union<'a> {
 
 struct Variant<'a> {
     name: String,
-    ty: String,
+    /// The swift type of the variants data, or None for unit variants
+    ty: Option<String>,
     serde_name: &'a str,
+    recursive: bool,
 }
 
 impl<'a> From<&'a types::UnionVariant> for Variant<'a> {
     fn from(val: &'a types::UnionVariant) -> Self {
         Variant {
-            name: to_camel_case(
+            name: escape_identifier(to_camel_case(
                 val.name
                     .as_ref()
                     .expect("union variants to generally have names"),
-            ),
-            ty: val.ty.swift_type(),
+            )),
+            ty: val.ty.as_ref().map(|ty| ty.swift_type()),
             serde_name: &val.serialized_name,
+            recursive: val
+                .ty
+                .as_ref()
+                .map(|ty| ty.contains_recursive_type())
+                .unwrap_or_default(),
         }
     }
 }
@@ -49,12 +56,19 @@ impl<'a> From<&'a types::UnionVariant> for Variant<'a> {
 impl fmt::Display for Union<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = &self.name;
-        writeln!(f, "public enum {name} {{")?;
-        writeln_for!(
-            indented(f),
-            Variant{ name, ty, .. } in &self.variants,
-            "case {name}({ty})"
-        );
+        // Enums that contain themselves need to be marked as indirect in swift
+        let indirect = if self.variants.iter().any(|v| v.recursive) {
+            "indirect "
+        } else {
+            ""
+        };
+        writeln!(f, "public {indirect}enum {name} {{")?;
+        for Variant { name, ty, .. } in &self.variants {
+            match ty {
+                Some(ty) => writeln!(indented(f), "case {name}({ty})")?,
+                None => writeln!(indented(f), "case {name}")?,
+            }
+        }
         let coding_keys = CodingKeys::new().with_fields(&self.variants);
         writeln!(indented(f), "{coding_keys}")?;
         writeln!(f, "}}\n")?;
@@ -103,7 +117,15 @@ impl fmt::Display for Union<'_> {
                     variants: &self.variants,
                 });
             }
-            UnionRepresentation::Untagged => todo!("Support untagged variants for swift"),
+            UnionRepresentation::Untagged => {
+                codable.encodable(UntaggedEncodable {
+                    variants: &self.variants,
+                });
+                codable.decodable(UntaggedDecodable {
+                    name,
+                    variants: &self.variants,
+                });
+            }
         }
         writeln!(f, "{codable}")?;
 
@@ -130,6 +152,33 @@ impl fmt::Display for ExternallyTaggedEncodable<'_> {
         let ExternallyTaggedEncodable { name, variants } = self;
         let coding_keys = format!("{name}.CodingKeys");
 
+        if variants.iter().any(|v| v.ty.is_none()) {
+            // Unit variants are encoded as a plain string so we can't share one keyed
+            // container between all the cases
+            writeln!(f, "switch self {{")?;
+            for Variant { name, ty, .. } in variants.iter() {
+                match ty {
+                    Some(_) => writedoc!(
+                        indented(f),
+                        r#"
+                            case .{name}(let data):
+                                var container = encoder.container(keyedBy: {coding_keys}.self)
+                                return try container.encode(data, forKey: .{name})
+                        "#
+                    )?,
+                    None => writedoc!(
+                        indented(f),
+                        r#"
+                            case .{name}:
+                                var container = encoder.singleValueContainer()
+                                return try container.encode({coding_keys}.{name})
+                        "#
+                    )?,
+                }
+            }
+            return write!(f, "}}");
+        }
+
         writeln!(
             f,
             "var container = encoder.container(keyedBy: {coding_keys}.self)"
@@ -157,13 +206,42 @@ impl fmt::Display for ExternallyTaggedDecodable<'_> {
         let ExternallyTaggedDecodable { name, variants } = self;
         let coding_keys = format!("{name}.CodingKeys");
 
+        if variants.iter().any(|v| v.ty.is_none()) {
+            writedoc!(
+                f,
+                r#"
+                    if let key = try? decoder.singleValueContainer().decode({coding_keys}.self) {{
+                        switch key {{
+                "#
+            )?;
+            for Variant { name, .. } in variants.iter().filter(|v| v.ty.is_none()) {
+                writedoc!(
+                    indented(&mut indented(f)),
+                    r#"
+                        case .{name}:
+                            self = .{name}
+                            return
+                    "#
+                )?;
+            }
+            writedoc!(
+                f,
+                r#"
+                            default:
+                                break
+                        }}
+                    }}
+                "#
+            )?;
+        }
+
         writeln!(
             f,
             "let container = try decoder.container(keyedBy: {coding_keys}.self)"
         )?;
         writedoc_for!(
             &mut *f,
-            Variant { name, ty, ..  } in variants.iter(),
+            (name, ty) in variants.iter().filter_map(|v| Some((&v.name, v.ty.as_ref()?))),
             r#"
                 if (container.contains(.{name})) {{
                     self = .{name}(try container.decode({ty}.self, forKey: .{name}))
@@ -183,7 +261,6 @@ impl fmt::Display for ExternallyTaggedDecodable<'_> {
     }
 }
 
-#[allow(dead_code)]
 struct InternallyTaggedEncodable<'a> {
     name: &'a str,
     tag: &'a str,
@@ -191,8 +268,46 @@ struct InternallyTaggedEncodable<'a> {
 }
 
 impl fmt::Display for InternallyTaggedEncodable<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!("Finish internally tagged enum support")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let InternallyTaggedEncodable {
+            name,
+            variants,
+            tag,
+        } = self;
+        let coding_keys = format!("{name}.CodingKeys");
+
+        writedoc!(
+            f,
+            r#"
+                enum TagCoding: String, CodingKey {{
+                    case tag = "{tag}"
+                }}
+                switch self {{
+            "#
+        )?;
+        for Variant { name, ty, .. } in *variants {
+            match ty {
+                // The tag gets encoded alongside the fields of the data
+                Some(_) => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}(let data):
+                            try data.encode(to: encoder)
+                            var container = encoder.container(keyedBy: TagCoding.self)
+                            try container.encode({coding_keys}.{name}, forKey: .tag)
+                    "#
+                )?,
+                None => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            var container = encoder.container(keyedBy: TagCoding.self)
+                            try container.encode({coding_keys}.{name}, forKey: .tag)
+                    "#
+                )?,
+            }
+        }
+        writeln!(f, "}}")
     }
 }
 
@@ -222,21 +337,30 @@ impl fmt::Display for InternallyTaggedDecodable<'_> {
                 switch key {{
             "#
         )?;
-        writedoc_for!(
-            indented(f),
-            Variant { name, ty, ..  } in *variants,
-            r#"
-                case .{name}:
-                    // Not 100% sure this'll work but
-                    let container = try decoder.singleValueContainer()
-                    self = .{name}(try container.decode({ty}.self))
-            "#
-        );
+        for Variant { name, ty, .. } in *variants {
+            match ty {
+                Some(ty) => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            // Not 100% sure this'll work but
+                            let container = try decoder.singleValueContainer()
+                            self = .{name}(try container.decode({ty}.self))
+                    "#
+                )?,
+                None => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            self = .{name}
+                    "#
+                )?,
+            }
+        }
         writeln!(f, "}}")
     }
 }
 
-#[allow(dead_code)]
 struct AdjacentlyTaggedEncodable<'a> {
     name: &'a str,
     tag: &'a str,
@@ -265,15 +389,25 @@ impl fmt::Display for AdjacentlyTaggedEncodable<'_> {
                 switch self {{
             "#
         )?;
-        writedoc_for!(
-            indented(f),
-            Variant { name,  ..  } in *variants,
-            r#"
-                case .{name}(let data):
-                    try container.encode({coding_keys}.{name}, forKey: .tag)
-                    try container.encode(data, forKey: .data)
-            "#
-        );
+        for Variant { name, ty, .. } in *variants {
+            match ty {
+                Some(_) => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}(let data):
+                            try container.encode({coding_keys}.{name}, forKey: .tag)
+                            try container.encode(data, forKey: .data)
+                    "#
+                )?,
+                None => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            try container.encode({coding_keys}.{name}, forKey: .tag)
+                    "#
+                )?,
+            }
+        }
         writeln!(f, "}}")
     }
 }
@@ -307,14 +441,98 @@ impl fmt::Display for AdjacentlyTaggedDecodable<'_> {
                 switch key {{
             "#
         )?;
+        for Variant { name, ty, .. } in *variants {
+            match ty {
+                Some(ty) => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            self = .{name}(try container.decode({ty}.self, forKey: .data))
+                    "#
+                )?,
+                None => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            self = .{name}
+                    "#
+                )?,
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+struct UntaggedEncodable<'a> {
+    variants: &'a [Variant<'a>],
+}
+
+impl fmt::Display for UntaggedEncodable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "var container = encoder.singleValueContainer()")?;
+        writeln!(f, "switch self {{")?;
+        for Variant { name, ty, .. } in self.variants {
+            match ty {
+                Some(_) => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}(let data):
+                            try container.encode(data)
+                    "#
+                )?,
+                None => writedoc!(
+                    indented(f),
+                    r#"
+                        case .{name}:
+                            try container.encodeNil()
+                    "#
+                )?,
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+struct UntaggedDecodable<'a> {
+    name: &'a str,
+    variants: &'a [Variant<'a>],
+}
+
+impl fmt::Display for UntaggedDecodable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let UntaggedDecodable { name, variants } = self;
+
+        writeln!(f, "let container = try decoder.singleValueContainer()")?;
+        // Unit variants are serialized as null, serde picks the first one on the way back in
+        if let Some(Variant { name, .. }) = variants.iter().find(|v| v.ty.is_none()) {
+            writedoc!(
+                f,
+                r#"
+                    if container.decodeNil() {{
+                        self = .{name}
+                        return
+                    }}
+                "#
+            )?;
+        }
         writedoc_for!(
-            indented(f),
-            Variant { name, ty, ..  } in *variants,
+            &mut *f,
+            (name, ty) in variants.iter().filter_map(|v| Some((&v.name, v.ty.as_ref()?))),
             r#"
-                case .{name}:
-                    self = .{name}(try container.decode({ty}.self, forKey: .data))
+                if let data = try? container.decode({ty}.self) {{
+                    self = .{name}(data)
+                    return
+                }}
             "#
         );
-        writeln!(f, "}}")
+        writedoc!(
+            f,
+            r#"
+                throw NSError(
+                    domain: "",
+                    code: 400,
+                    userInfo: [ NSLocalizedDescriptionKey: "Data did not match any variant of {name}"]
+                )"#
+        )
     }
 }
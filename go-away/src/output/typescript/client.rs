@@ -0,0 +1,378 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+use indenter::indented;
+use indoc::writedoc;
+
+use super::{
+    keywords::{identifier, string_literal},
+    wide_integers::reviver,
+    ConfigurableFormat, FieldType, Operation, PathSegment, TypeScriptClientOptions,
+    TypeScriptWideIntegers,
+};
+
+/// The names used in the body of each client function, which parameters can't share
+const LOCALS: &[&str] = &[
+    "config",
+    "query",
+    "body",
+    "request",
+    "encodeURIComponent",
+    "String",
+    "toBigInt",
+    "reviveList",
+    "reviveMap",
+];
+
+/// Names declared at the top of the client or used by `request`, which operation functions
+/// mustn't shadow
+const MODULE_NAMES: &[&str] = &[
+    "ClientConfig",
+    "RequestError",
+    "request",
+    "fetch",
+    "encodeURIComponent",
+    "URLSearchParams",
+    "reviveBigInts",
+    "replaceBigInts",
+    "toBigInt",
+    "reviveList",
+    "reviveMap",
+];
+
+/// A function in a typescript API client, which calls an `Operation` with `fetch`
+///
+/// This shouldn't be instantiated directly but passed using turbofish operator
+/// to `registry_to_client`.  The client refers to types by name, so the output of
+/// `registry_to_output::<TypeScriptType>` is needed alongside it - either in the same file
+/// or imported with `TypeScriptClientOptions::with_types_module`.
+pub struct TypeScriptClient<'a>(pub &'a Operation);
+
+impl ConfigurableFormat for TypeScriptClient<'_> {
+    type Options = TypeScriptClientOptions;
+
+    fn fmt_with_options(
+        &self,
+        options: &TypeScriptClientOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let operation = self.0;
+        let type_options = &options.types;
+
+        let mut params = vec!["config: ClientConfig".to_string()];
+        for param in &operation.path_params {
+            params.push(format!(
+                "{}: {}",
+                param_name(&param.name),
+                param.ty.typescript_type(type_options)
+            ));
+        }
+        let mut request_options = vec![];
+        if let Some(query) = &operation.query {
+            params.push(format!("query: {}", query.typescript_type(type_options)));
+            request_options.push("query".to_string());
+        }
+        if let Some(request) = &operation.request {
+            params.push(format!("body: {}", request.typescript_type(type_options)));
+            request_options.push("body".to_string());
+        }
+        let (response_reviver, error_reviver) = revivers(operation, options);
+        if let Some(reviver) = response_reviver {
+            request_options.push(format!("revive: {reviver}"));
+        }
+        if let Some(reviver) = error_reviver {
+            request_options.push(format!("reviveError: {reviver}"));
+        }
+
+        let response = operation
+            .response
+            .as_ref()
+            .map(|ty| ty.typescript_type(type_options))
+            .unwrap_or_else(|| "void".to_string());
+        let error = operation
+            .error
+            .as_ref()
+            .map(|ty| ty.typescript_type(type_options))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        writedoc!(
+            f,
+            "
+
+            /**
+             * `{} {}`
+             *
+             * Throws a `RequestError<{error}>` if the server responds with an error status.
+             */
+            ",
+            operation.method.as_str(),
+            operation.path,
+        )?;
+        writeln!(
+            f,
+            "export function {}({}): Promise<{response}> {{",
+            function_name(&operation.name),
+            params.join(", ")
+        )?;
+        let request_options = match request_options.is_empty() {
+            true => "{}".to_string(),
+            false => format!("{{ {} }}", request_options.join(", ")),
+        };
+        writeln!(
+            indented(f),
+            "return request<{response}, {error}>(config, \"{}\", {}, {request_options});",
+            operation.method.as_str(),
+            path_expr(operation),
+        )?;
+        writeln!(f, "}}")
+    }
+
+    fn fmt_prelude(
+        operations: &[Self],
+        options: &TypeScriptClientOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if operations.is_empty() {
+            return Ok(());
+        }
+
+        let bigints = options.types.wide_integers == TypeScriptWideIntegers::BigInt;
+
+        if let Some(module) = &options.types_module {
+            let mut names = BTreeSet::new();
+            for TypeScriptClient(operation) in operations {
+                let types = operation
+                    .path_params
+                    .iter()
+                    .map(|param| &param.ty)
+                    .chain(&operation.query)
+                    .chain(&operation.request)
+                    .chain(&operation.response)
+                    .chain(&operation.error);
+                for ty in types {
+                    ty.named_types(&mut names);
+                }
+            }
+            if !names.is_empty() {
+                let names = names.into_iter().collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "import type {{ {} }} from \"{module}\";\n",
+                    names.join(", ")
+                )?;
+            }
+
+            if bigints {
+                let mut functions = BTreeSet::from(["replaceBigInts", "reviveBigInts"]);
+                let mut used_revivers = vec![];
+                for TypeScriptClient(operation) in operations {
+                    let (response, error) = revivers(operation, options);
+                    used_revivers.extend(response.into_iter().chain(error));
+                }
+                // Revivers are just function names & calls, e.g. `reviveList(toBigInt)`
+                functions.extend(
+                    used_revivers
+                        .iter()
+                        .flat_map(|reviver| reviver.split(['(', ')']))
+                        .filter(|name| !name.is_empty()),
+                );
+                let functions = functions.into_iter().collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "import {{ {} }} from \"{module}\";\n",
+                    functions.join(", ")
+                )?;
+            }
+        }
+
+        let (options_type, replacer) = match bigints {
+            true => (
+                "{ query?: object; body?: unknown; revive?: (value: any) => T; reviveError?: (value: any) => E }",
+                ", replaceBigInts",
+            ),
+            false => ("{ query?: object; body?: unknown }", ""),
+        };
+
+        writedoc!(
+            f,
+            r#"
+                export type ClientConfig = {{
+                    /** The URL the API is served from, e.g. `https://example.com/api` */
+                    baseUrl: string;
+                    /** Headers to send with every request */
+                    headers?: Record<string, string>;
+                    /** An alternative implementation of `fetch` */
+                    fetch?: typeof fetch;
+                }}
+
+                /** The error thrown when the server responds with an error status */
+                export class RequestError<E> extends Error {{
+                    readonly status: number;
+                    readonly body: E;
+
+                    constructor(status: number, body: E) {{
+                        super(`request failed with status ${{status}}`);
+                        this.status = status;
+                        this.body = body;
+                    }}
+                }}
+
+                async function request<T, E>(
+                    config: ClientConfig,
+                    method: string,
+                    path: string,
+                    options: {options_type},
+                ): Promise<T> {{
+                    let url = config.baseUrl.replace(/\/+$/, "") + path;
+                    if (options.query !== undefined) {{
+                        const params = new URLSearchParams();
+                        for (const [key, value] of Object.entries(options.query)) {{
+                            for (const item of Array.isArray(value) ? value : [value]) {{
+                                if (item !== undefined && item !== null) {{
+                                    params.append(key, String(item));
+                                }}
+                            }}
+                        }}
+                        const search = params.toString();
+                        if (search !== "") {{
+                            url += `?${{search}}`;
+                        }}
+                    }}
+
+                    const headers: Record<string, string> = {{ Accept: "application/json" }};
+                    if (options.body !== undefined) {{
+                        headers["Content-Type"] = "application/json";
+                    }}
+                    const response = await (config.fetch ?? fetch)(url, {{
+                        method,
+                        headers: {{ ...headers, ...config.headers }},
+                        body: options.body === undefined ? undefined : JSON.stringify(options.body{replacer}),
+                    }});
+
+                    const text = await response.text();
+            "#
+        )?;
+        match bigints {
+            true => writedoc!(
+                f,
+                r#"
+                        const data: unknown = text === "" ? undefined : JSON.parse(text, reviveBigInts);
+                        if (!response.ok) {{
+                            const body = options.reviveError === undefined ? data : options.reviveError(data);
+                            throw new RequestError<E>(response.status, body as E);
+                        }}
+                        return (options.revive === undefined ? data : options.revive(data)) as T;
+                    }}
+                "#
+            ),
+            false => writedoc!(
+                f,
+                r#"
+                        const data: unknown = text === "" ? undefined : JSON.parse(text);
+                        if (!response.ok) {{
+                            throw new RequestError<E>(response.status, data as E);
+                        }}
+                        return data as T;
+                    }}
+                "#
+            ),
+        }
+    }
+}
+
+/// A typescript expression for the path of `operation`, with its parameters substituted in
+fn path_expr(operation: &Operation) -> String {
+    let segments = operation.path_segments();
+    if !segments
+        .iter()
+        .any(|segment| matches!(segment, PathSegment::Param(_)))
+    {
+        return string_literal(&operation.path);
+    }
+
+    let mut expr = "`".to_string();
+    for segment in segments {
+        match segment {
+            PathSegment::Literal(literal) => expr.push_str(
+                &literal
+                    .replace('\\', "\\\\")
+                    .replace('`', "\\`")
+                    .replace("${", "\\${"),
+            ),
+            PathSegment::Param(name) => write!(
+                expr,
+                "${{encodeURIComponent(String({}))}}",
+                param_name(name)
+            )
+            .unwrap(),
+        }
+    }
+    expr.push('`');
+    expr
+}
+
+/// The revivers for the response & error of `operation`, if the types hold bigints
+fn revivers(
+    operation: &Operation,
+    options: &TypeScriptClientOptions,
+) -> (Option<String>, Option<String>) {
+    if options.types.wide_integers != TypeScriptWideIntegers::BigInt {
+        return (None, None);
+    }
+    // Every type has a reviver when the types use bigints, even if it does nothing
+    let reviver = |ty: &FieldType| reviver(ty, &|_| true);
+    (
+        operation.response.as_ref().and_then(reviver),
+        operation.error.as_ref().and_then(reviver),
+    )
+}
+
+fn function_name(name: &str) -> String {
+    let name = camel_case(name);
+    match MODULE_NAMES.contains(&name.as_str()) {
+        true => format!("{name}_"),
+        false => identifier(&name),
+    }
+}
+
+fn param_name(name: &str) -> String {
+    let name = camel_case(name);
+    match LOCALS.contains(&name.as_str()) {
+        true => format!("{name}_"),
+        false => identifier(&name),
+    }
+}
+
+/// Converts a snake case name to camel case, e.g. `get_user` to `getUser`
+fn camel_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        match c {
+            '_' if !output.is_empty() => upper = true,
+            c if upper => {
+                output.push(c.to_ascii_uppercase());
+                upper = false;
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+impl FieldType {
+    /// Adds the names of any named types used by this type to `names`
+    fn named_types(&self, names: &mut BTreeSet<String>) {
+        match self {
+            FieldType::Named(type_ref) => {
+                names.insert(type_ref.name().to_string());
+            }
+            FieldType::Optional(inner) | FieldType::List(inner) => inner.named_types(names),
+            FieldType::Map { key, value } => {
+                key.named_types(names);
+                value.named_types(names);
+            }
+            FieldType::Primitive(_) => {}
+        }
+    }
+}
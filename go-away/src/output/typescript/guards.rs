@@ -0,0 +1,303 @@
+use std::fmt::{self, Write};
+
+use indenter::indented;
+use indoc::writedoc;
+
+use super::{
+    typescript_int, FieldType, Primitive, Property, Struct, TypeScriptEnumStyle, TypeScriptOptions,
+    TypeScriptType, Union, UnionRepresentation, UnionVariant,
+};
+
+/// Writes the `is<Name>` & `assert<Name>` runtime type guards for a type.
+///
+/// These check the shape of a value that came from `JSON.parse`, so they only need
+/// to handle the things that JSON can represent.
+pub struct TypeGuards<'a>(pub &'a TypeScriptType<'a>, pub &'a TypeScriptOptions);
+
+impl fmt::Display for TypeGuards<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.0 {
+            TypeScriptType::Struct(details) => &details.name,
+            TypeScriptType::NewType(details) => &details.name,
+            TypeScriptType::Alias(details) => &details.name,
+            TypeScriptType::Enum(details) => &details.name,
+            TypeScriptType::Union(details) => &details.name,
+        };
+
+        writeln!(
+            f,
+            "export function is{name}(value: unknown): value is {name} {{"
+        )?;
+        match self.0 {
+            TypeScriptType::Struct(details) => {
+                write!(indented(f), "{}", StructGuard(details, self.1))?
+            }
+            TypeScriptType::NewType(details) => writeln!(
+                indented(f),
+                "return {};",
+                details.inner.typescript_guard("value", 0, self.1)
+            )?,
+            TypeScriptType::Alias(details) => writeln!(
+                indented(f),
+                "return {};",
+                details.inner.typescript_guard("value", 0, self.1)
+            )?,
+            TypeScriptType::Enum(_) => match self.1.enum_style {
+                TypeScriptEnumStyle::Enum => writeln!(
+                    indented(f),
+                    "return Object.values({name}).includes(value as {name});"
+                )?,
+                TypeScriptEnumStyle::StringUnion => writeln!(
+                    indented(f),
+                    "return ({name}Values as readonly unknown[]).includes(value);"
+                )?,
+            },
+            TypeScriptType::Union(details) => {
+                write!(indented(f), "{}", UnionGuard(details, self.1))?
+            }
+        }
+        writeln!(f, "}}\n")?;
+
+        writedoc!(
+            f,
+            r#"
+                export function assert{name}(value: unknown): asserts value is {name} {{
+                    if (!is{name}(value)) {{
+                        throw new TypeError("value is not a valid {name}");
+                    }}
+                }}
+            "#
+        )
+    }
+}
+
+/// Returns false if `value` isn't a JSON object, otherwise makes it available as `record`
+struct RecordCheck;
+
+impl fmt::Display for RecordCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writedoc!(
+            f,
+            r#"
+                if (typeof value !== "object" || value === null || Array.isArray(value)) {{
+                    return false;
+                }}
+                const record = value as Record<string, unknown>;
+            "#
+        )
+    }
+}
+
+struct StructGuard<'a>(&'a Struct, &'a TypeScriptOptions);
+
+impl fmt::Display for StructGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{RecordCheck}")?;
+        let conditions = self
+            .0
+            .fields
+            .iter()
+            .map(|field| {
+                let expr = format!("record[\"{}\"]", field.serialized_name);
+                let property = Property::new(field, self.1);
+                let guard = property.ty.typescript_guard(&expr, 0, self.1);
+                match property.optional {
+                    true => format!("({expr} === undefined || {})", group(guard)),
+                    false => guard,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match conditions.as_slice() {
+            [] => writeln!(f, "return true;"),
+            [condition] => writeln!(f, "return {condition};"),
+            conditions => {
+                writeln!(f, "return (")?;
+                writeln!(indented(f), "{}", conditions.join(" &&\n"))?;
+                writeln!(f, ");")
+            }
+        }
+    }
+}
+
+struct UnionGuard<'a>(&'a Union, &'a TypeScriptOptions);
+
+impl fmt::Display for UnionGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        match &details.representation {
+            UnionRepresentation::ExternallyTagged => {
+                // Unit variants are serialized as a plain string
+                let units = details
+                    .variants
+                    .iter()
+                    .filter(|variant| variant.ty.is_none())
+                    .map(|variant| format!("value === \"{}\"", variant.serialized_name))
+                    .collect::<Vec<_>>();
+                if !units.is_empty() {
+                    writeln!(f, "if ({}) {{", units.join(" || "))?;
+                    writeln!(indented(f), "return true;")?;
+                    writeln!(f, "}}")?;
+                }
+                write!(f, "{RecordCheck}")?;
+                writedoc!(
+                    f,
+                    r#"
+                        const keys = Object.keys(record);
+                        if (keys.length !== 1) {{
+                            return false;
+                        }}
+                    "#
+                )?;
+                write!(
+                    f,
+                    "{}",
+                    TagSwitch {
+                        tag_expr: "keys[0]",
+                        variants: details.variants.iter().filter(|v| v.ty.is_some()).collect(),
+                        content: ContentExpr::Field,
+                        options: self.1,
+                    }
+                )?;
+            }
+            UnionRepresentation::InternallyTagged { tag } => {
+                write!(f, "{RecordCheck}")?;
+                write!(
+                    f,
+                    "{}",
+                    TagSwitch {
+                        tag_expr: &format!("record[\"{tag}\"]"),
+                        variants: details.variants.iter().collect(),
+                        content: ContentExpr::Value,
+                        options: self.1,
+                    }
+                )?;
+            }
+            UnionRepresentation::AdjacentlyTagged { tag, content } => {
+                write!(f, "{RecordCheck}")?;
+                let tag = format!("record[\"{tag}\"]");
+                let content = format!("record[\"{content}\"]");
+                write!(
+                    f,
+                    "{}",
+                    TagSwitch {
+                        tag_expr: &tag,
+                        variants: details.variants.iter().collect(),
+                        content: ContentExpr::Fixed(&content),
+                        options: self.1,
+                    }
+                )?;
+            }
+            UnionRepresentation::Untagged => {
+                let conditions = details
+                    .variants
+                    .iter()
+                    .map(|variant| match &variant.ty {
+                        Some(ty) => group(ty.typescript_guard("value", 0, self.1)),
+                        // Untagged unit variants are serialized as null
+                        None => "value === null".to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                writeln!(f, "return {};", conditions.join(" || "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the data for a variant lives, relative to the tag
+enum ContentExpr<'a> {
+    /// The data is the whole value, alongside the tag
+    Value,
+    /// The data is in the field of `record` named after the tag
+    Field,
+    /// The data is always at this expression
+    Fixed(&'a str),
+}
+
+/// A switch on the tag of a union that checks the data of whichever variant is present
+struct TagSwitch<'a> {
+    tag_expr: &'a str,
+    variants: Vec<&'a UnionVariant>,
+    content: ContentExpr<'a>,
+    options: &'a TypeScriptOptions,
+}
+
+impl fmt::Display for TagSwitch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "switch ({}) {{", self.tag_expr)?;
+        for variant in &self.variants {
+            let f = &mut indented(f);
+            writeln!(f, "case \"{}\":", variant.serialized_name)?;
+            let condition = match &variant.ty {
+                None => "true".to_string(),
+                Some(ty) => match self.content {
+                    ContentExpr::Value => ty.typescript_guard("value", 0, self.options),
+                    ContentExpr::Field => ty.typescript_guard(
+                        &format!("record[\"{}\"]", variant.serialized_name),
+                        0,
+                        self.options,
+                    ),
+                    ContentExpr::Fixed(expr) => ty.typescript_guard(expr, 0, self.options),
+                },
+            };
+            writeln!(indented(f), "return {condition};")?;
+        }
+        writeln!(indented(f), "default:")?;
+        writeln!(indented(&mut indented(f)), "return false;")?;
+        writeln!(f, "}}")
+    }
+}
+
+impl FieldType {
+    /// A typescript expression that checks whether `expr` holds a value of this type.
+    ///
+    /// `depth` is used to give the parameters of nested callbacks unique names.
+    fn typescript_guard(&self, expr: &str, depth: usize, options: &TypeScriptOptions) -> String {
+        let suffix = match depth {
+            0 => String::new(),
+            depth => depth.to_string(),
+        };
+        match self {
+            FieldType::Named(type_ref) => format!("is{}({expr})", type_ref.name()),
+            FieldType::Optional(inner) => format!(
+                "({expr} === null || {})",
+                group(inner.typescript_guard(expr, depth, options))
+            ),
+            FieldType::List(inner) => {
+                let item = format!("item{suffix}");
+                format!(
+                    "Array.isArray({expr}) && {expr}.every(({item}) => {})",
+                    inner.typescript_guard(&item, depth + 1, options)
+                )
+            }
+            FieldType::Map { value, .. } => {
+                // JSON object keys are always strings, so only the values are checked
+                let entry = format!("entry{suffix}");
+                format!(
+                    "typeof {expr} === \"object\" && {expr} !== null && !Array.isArray({expr}) && \
+                     Object.values({expr} as Record<string, unknown>).every(({entry}) => {})",
+                    value.typescript_guard(&entry, depth + 1, options)
+                )
+            }
+            FieldType::Primitive(Primitive::String) | FieldType::Primitive(Primitive::Time) => {
+                format!("typeof {expr} === \"string\"")
+            }
+            FieldType::Primitive(Primitive::Float) => format!("typeof {expr} === \"number\""),
+            FieldType::Primitive(Primitive::Int(kind)) => match typescript_int(*kind, options) {
+                "bigint" => format!("typeof {expr} === \"bigint\""),
+                "string" => format!("typeof {expr} === \"string\""),
+                _ => format!("Number.isInteger({expr})"),
+            },
+            FieldType::Primitive(Primitive::Bool) => format!("typeof {expr} === \"boolean\""),
+        }
+    }
+}
+
+/// Wraps a condition in brackets if it has several parts
+fn group(condition: String) -> String {
+    if condition.contains(" && ") && !condition.starts_with('(') {
+        return format!("({condition})");
+    }
+    condition
+}
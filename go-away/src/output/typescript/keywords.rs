@@ -0,0 +1,113 @@
+use std::fmt::Write;
+
+/// TypeScript's reserved words
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Formats a property name, quoting it if it's a reserved word or isn't a valid
+/// identifier (e.g. `content-type`)
+pub fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let valid_identifier = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        .unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if !valid_identifier || KEYWORDS.contains(&name) {
+        return string_literal(name);
+    }
+    name.to_string()
+}
+
+/// Formats a variable or function name, adding a trailing underscore if it's a reserved word
+pub fn identifier(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("{name}_"),
+        false => name.to_string(),
+    }
+}
+
+/// Formats a javascript string literal, escaping anything that can't appear inside one as
+/// it is
+pub fn string_literal(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            // Line & paragraph separators end a line in older runtimes
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                write!(output, "\\u{:04x}", c as u32).unwrap()
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_name() {
+        assert_eq!(property_name("plain_name"), "plain_name");
+        assert_eq!(property_name("default"), r#""default""#);
+        assert_eq!(property_name("content-type"), r#""content-type""#);
+        assert_eq!(
+            property_name("a \"b\" \\ ✓\n\u{0}\u{2028}"),
+            r#""a \"b\" \\ ✓\n\u0000\u2028""#
+        );
+    }
+}
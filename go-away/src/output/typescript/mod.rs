@@ -2,6 +2,23 @@ use std::{fmt, fmt::Write};
 
 use indenter::indented;
 
+mod client;
+mod guards;
+pub(super) mod keywords;
+mod options;
+mod wide_integers;
+
+use super::ConfigurableFormat;
+use guards::TypeGuards;
+use keywords::property_name;
+use wide_integers::{BigIntFunctions, WideIntegers};
+
+pub use client::TypeScriptClient;
+pub use options::{
+    TypeScriptClientOptions, TypeScriptEnumStyle, TypeScriptOptions, TypeScriptStructStyle,
+    TypeScriptWideIntegers,
+};
+
 pub use crate::types::*;
 
 /// An enum representing the possible top-level types in TypeScript
@@ -23,20 +40,55 @@ pub enum TypeScriptType<'a> {
 
 impl fmt::Display for TypeScriptType<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.fmt_with_options(&TypeScriptOptions::default(), f)
+    }
+}
+
+impl ConfigurableFormat for TypeScriptType<'_> {
+    type Options = TypeScriptOptions;
+
+    fn fmt_with_options(
+        &self,
+        options: &TypeScriptOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
         match self {
             TypeScriptType::Struct(details) => {
-                writeln!(f, "export type {} = {{", details.name)?;
+                match options.struct_style {
+                    TypeScriptStructStyle::TypeAlias => {
+                        writeln!(f, "export type {} = {{", details.name)?
+                    }
+                    TypeScriptStructStyle::Interface => {
+                        writeln!(f, "export interface {} {{", details.name)?
+                    }
+                }
                 for field in &details.fields {
-                    writeln!(indented(f), "{}", TypeScriptField(field))?;
+                    writeln!(indented(f), "{}", TypeScriptField(field, options))?;
                 }
                 writeln!(f, "}}")?;
             }
+            TypeScriptType::NewType(details) if options.branded_newtypes => {
+                let name = &details.name;
+                let inner = details.inner.typescript_type(options);
+                let brand = format!("{{ readonly __brand: \"{name}\" }}");
+                // null can't carry a brand, so optional newtypes only brand the non-null part
+                let branded = match &details.inner {
+                    FieldType::Optional(present) => {
+                        format!("({} & {brand}) | null", present.typescript_type(options))
+                    }
+                    _ => format!("{inner} & {brand}"),
+                };
+                writeln!(f, "export type {name} = {branded};")?;
+                writeln!(f, "export function {name}(value: {inner}): {name} {{")?;
+                writeln!(indented(f), "return value as {name};")?;
+                writeln!(f, "}}")?;
+            }
             TypeScriptType::NewType(details) => {
                 writeln!(
                     f,
                     "export type {} = {};",
                     details.name,
-                    details.inner.typescript_type()
+                    details.inner.typescript_type(options)
                 )?;
             }
             TypeScriptType::Alias(details) => {
@@ -44,30 +96,49 @@ impl fmt::Display for TypeScriptType<'_> {
                     f,
                     "export type {} = {};",
                     details.name,
-                    details.inner.typescript_type()
+                    details.inner.typescript_type(options)
                 )?;
             }
-            TypeScriptType::Enum(details) => {
-                writeln!(f, "export enum {} {{", details.name)?;
-                for variant in &details.variants {
+            TypeScriptType::Enum(details) => match options.enum_style {
+                TypeScriptEnumStyle::Enum => {
+                    writeln!(f, "export enum {} {{", details.name)?;
+                    for variant in &details.variants {
+                        writeln!(
+                            indented(f),
+                            "{} = \"{}\",",
+                            property_name(&variant.name),
+                            variant.serialized_name
+                        )?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+                TypeScriptEnumStyle::StringUnion => {
+                    let values = details
+                        .variants
+                        .iter()
+                        .map(|variant| format!("\"{}\"", variant.serialized_name))
+                        .collect::<Vec<_>>();
+                    let union = match values.is_empty() {
+                        true => "never".to_string(),
+                        false => values.join(" | "),
+                    };
+                    writeln!(f, "export type {} = {union};", details.name)?;
                     writeln!(
-                        indented(f),
-                        "{} = \"{}\",",
-                        variant.name,
-                        variant.serialized_name
+                        f,
+                        "export const {}Values = [{}] as const;",
+                        details.name,
+                        values.join(", ")
                     )?;
                 }
-                writeln!(f, "}}")?;
-            }
+            },
             TypeScriptType::Union(details) => match &details.representation {
                 UnionRepresentation::ExternallyTagged => {
                     let mut union_types: Vec<String> = vec![];
                     for variant in &details.variants {
-                        union_types.push(format!(
-                            "{{ \"{}\": {} }}",
-                            variant.serialized_name,
-                            variant.typescript_name()
-                        ));
+                        union_types.push(match variant.typescript_name(options) {
+                            Some(ty) => format!("{{ \"{}\": {} }}", variant.serialized_name, ty),
+                            None => format!("\"{}\"", variant.serialized_name),
+                        });
                     }
                     writeln!(
                         f,
@@ -79,12 +150,13 @@ impl fmt::Display for TypeScriptType<'_> {
                 UnionRepresentation::InternallyTagged { tag } => {
                     let mut union_types: Vec<String> = vec![];
                     for variant in &details.variants {
-                        union_types.push(format!(
-                            "({{ \"{}\": \"{}\" }} & {})",
-                            tag,
-                            variant.serialized_name,
-                            variant.typescript_name()
-                        ));
+                        union_types.push(match variant.typescript_name(options) {
+                            Some(ty) => format!(
+                                "({{ \"{}\": \"{}\" }} & {})",
+                                tag, variant.serialized_name, ty
+                            ),
+                            None => format!("{{ \"{}\": \"{}\" }}", tag, variant.serialized_name),
+                        });
                     }
                     writeln!(
                         f,
@@ -96,7 +168,11 @@ impl fmt::Display for TypeScriptType<'_> {
                 UnionRepresentation::Untagged => {
                     let mut union_types: Vec<String> = vec![];
                     for variant in &details.variants {
-                        union_types.push(variant.typescript_name());
+                        union_types.push(
+                            variant
+                                .typescript_name(options)
+                                .unwrap_or_else(|| "null".to_string()),
+                        );
                     }
                     writeln!(
                         f,
@@ -108,13 +184,13 @@ impl fmt::Display for TypeScriptType<'_> {
                 UnionRepresentation::AdjacentlyTagged { tag, content } => {
                     let mut union_types: Vec<String> = vec![];
                     for variant in &details.variants {
-                        union_types.push(format!(
-                            "{{ \"{}\": \"{}\", \"{}\": {} }}",
-                            tag,
-                            variant.serialized_name,
-                            content,
-                            variant.typescript_name()
-                        ));
+                        union_types.push(match variant.typescript_name(options) {
+                            Some(ty) => format!(
+                                "{{ \"{}\": \"{}\", \"{}\": {} }}",
+                                tag, variant.serialized_name, content, ty
+                            ),
+                            None => format!("{{ \"{}\": \"{}\" }}", tag, variant.serialized_name),
+                        });
                     }
                     writeln!(
                         f,
@@ -126,54 +202,133 @@ impl fmt::Display for TypeScriptType<'_> {
             },
         }
 
+        // String union enums always get a guard, as it's the only way to check them at runtime
+        let string_union = matches!(self, TypeScriptType::Enum(_))
+            && options.enum_style == TypeScriptEnumStyle::StringUnion;
+        if options.type_guards || string_union {
+            writeln!(f, "\n{}", TypeGuards(self, options))?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_prelude(
+        types: &[Self],
+        options: &TypeScriptOptions,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let wide_integers = WideIntegers::new(types);
+        match options.wide_integers {
+            TypeScriptWideIntegers::Number | TypeScriptWideIntegers::String => {}
+            TypeScriptWideIntegers::Warn => {
+                let warnings = wide_integers.warnings().collect::<Vec<_>>();
+                for warning in &warnings {
+                    writeln!(f, "// warning: {warning}")?;
+                }
+                if !warnings.is_empty() {
+                    writeln!(f)?;
+                }
+            }
+            TypeScriptWideIntegers::BigInt => writeln!(f, "{}", BigIntFunctions(&wide_integers))?,
+        }
         Ok(())
     }
 }
 
-pub struct TypeScriptField<'a>(&'a Field);
+pub struct TypeScriptField<'a>(&'a Field, &'a TypeScriptOptions);
 
 impl fmt::Display for TypeScriptField<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let details = self.0;
+        let TypeScriptField(details, options) = self;
+        if options.readonly_properties {
+            write!(f, "readonly ")?;
+        }
+        let property = Property::new(details, options);
         write!(
             f,
-            r#"{}: {};"#,
-            details.serialized_name,
-            details.ty.typescript_type(),
+            "{}{}: {};",
+            property_name(&details.serialized_name),
+            if property.optional { "?" } else { "" },
+            property.ty.typescript_type(options),
         )
     }
 }
 
+/// How a field is represented as a typescript property
+struct Property<'a> {
+    /// Whether the property might be missing
+    optional: bool,
+    /// The type of the property when it's present
+    ty: &'a FieldType,
+}
+
+impl<'a> Property<'a> {
+    fn new(field: &'a Field, options: &TypeScriptOptions) -> Self {
+        let skipped_if = match &field.skip_serializing_if {
+            Some(function) if options.optional_properties => function,
+            _ => {
+                return Property {
+                    optional: false,
+                    ty: &field.ty,
+                }
+            }
+        };
+        let ty = match &field.ty {
+            FieldType::Optional(inner) if skipped_if.rsplit("::").next() == Some("is_none") => {
+                inner
+            }
+            ty => ty,
+        };
+        Property { optional: true, ty }
+    }
+}
+
 impl FieldType {
-    fn typescript_type(&self) -> String {
+    fn typescript_type(&self, options: &TypeScriptOptions) -> String {
         match self {
             FieldType::Named(type_ref) => type_ref.name().to_string(),
-            FieldType::Optional(inner) => format!("{} | null", inner.typescript_type()),
-            FieldType::List(inner) => format!("{}[]", inner.typescript_type()),
+            FieldType::Optional(inner) => format!("{} | null", inner.typescript_type(options)),
+            FieldType::List(inner) => format!("{}[]", inner.typescript_type(options)),
             FieldType::Map { key, value } => {
-                format!(
-                    "Record<{}, {}>",
-                    key.typescript_type(),
-                    value.typescript_type()
-                )
+                let key = match key.as_ref() {
+                    // Map keys are always strings in JSON, so these never lose precision
+                    // but can't be a bigint
+                    FieldType::Primitive(Primitive::Int(kind))
+                        if typescript_int(*kind, options) == "bigint" =>
+                    {
+                        "string".to_string()
+                    }
+                    key => key.typescript_type(options),
+                };
+                format!("Record<{key}, {}>", value.typescript_type(options))
             }
             FieldType::Primitive(Primitive::String) => "string".to_string(),
             FieldType::Primitive(Primitive::Float) => "number".to_string(),
-            FieldType::Primitive(Primitive::Int) => "number".to_string(),
+            FieldType::Primitive(Primitive::Int(kind)) => {
+                typescript_int(*kind, options).to_string()
+            }
             FieldType::Primitive(Primitive::Bool) => "boolean".to_string(),
             FieldType::Primitive(Primitive::Time) => "string".to_string(),
         }
     }
 }
 
+/// The typescript type for an integer of the given kind
+fn typescript_int(kind: IntKind, options: &TypeScriptOptions) -> &'static str {
+    if kind.fits_in_f64() {
+        return "number";
+    }
+    match options.wide_integers {
+        TypeScriptWideIntegers::Number | TypeScriptWideIntegers::Warn => "number",
+        TypeScriptWideIntegers::BigInt => "bigint",
+        TypeScriptWideIntegers::String => "string",
+    }
+}
+
 impl UnionVariant {
-    fn typescript_name(&self) -> String {
-        match (&self.name, &self.ty) {
-            (_, FieldType::Named(_)) => self.ty.typescript_type(),
-            (_, FieldType::Optional(_)) => self.ty.typescript_type(),
-            (Some(name), _) => name.clone(),
-            _ => todo!("Variant must be named or named type for now (fix this later)"),
-        }
+    /// The typescript type of the data in this variant, or `None` for unit variants
+    fn typescript_name(&self, options: &TypeScriptOptions) -> Option<String> {
+        Some(self.ty.as_ref()?.typescript_type(options))
     }
 }
 
@@ -182,7 +337,7 @@ mod tests {
     use insta::assert_snapshot;
 
     use super::*;
-    use crate::types::TypeRef;
+    use crate::types::{IntKind, TypeRef};
 
     #[test]
     fn test_primitive_structs() {
@@ -194,21 +349,33 @@ mod tests {
                         name: "a_string".into(),
                         serialized_name: "a_string".into(),
                         ty: FieldType::Primitive(Primitive::String),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "an_int".into(),
                         serialized_name: "renamed_tho".into(),
-                        ty: FieldType::Primitive(Primitive::Int),
+                        ty: FieldType::Primitive(Primitive::Int(IntKind::I64)),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "a_bool".into(),
                         serialized_name: "also_renamed".into(),
                         ty: FieldType::Primitive(Primitive::Bool),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                     Field {
                         name: "a_float".into(),
                         serialized_name: "a_float".into(),
                         ty: FieldType::Primitive(Primitive::Float),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
                     },
                 ],
             })
@@ -239,11 +406,7 @@ mod tests {
         // pub type Users = HashMap<UserId, UserData>;
         assert_snapshot!(TypeScriptType::NewType(&NewType {
             name: "Users".into(),
-            inner: FieldType::Map{ key: Box::new(FieldType::Named(TypeRef {
-                name: "UserId".into()
-            })), value: Box::new(FieldType::Named(TypeRef {
-                name: "UserData".into()
-            }))},
+            inner: FieldType::Map{ key: Box::new(FieldType::Named(TypeRef::new("UserId"))), value: Box::new(FieldType::Named(TypeRef::new("UserData")))},
         })
         .to_string(), @"export type Users = Record<UserId, UserData>;
 ");
@@ -254,11 +417,7 @@ mod tests {
         // pub type Users = HashMap<UserId, UserData>;
         assert_snapshot!(TypeScriptType::Alias(&Alias {
         name: "Users".into(),
-        inner: FieldType::Map{ key: Box::new(FieldType::Named(TypeRef {
-            name: "UserId".into()
-        })), value: Box::new(FieldType::Named(TypeRef {
-            name: "UserData".into()
-        }))},
+        inner: FieldType::Map{ key: Box::new(FieldType::Named(TypeRef::new("UserId"))), value: Box::new(FieldType::Named(TypeRef::new("UserData")))},
     })
     .to_string(), @"export type Users = Record<UserId, UserData>;
 ");
@@ -295,16 +454,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarOne".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                     serialized_name: "VAR_ONE".into(),
                 },
                 UnionVariant {
                     name: Some("VarTwo".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_TWO".into(),
                 }
             ]
@@ -323,16 +478,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarOne".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                     serialized_name: "VAR_ONE".into(),
                 },
                 UnionVariant {
                     name: Some("VarTwo".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_TWO".into(),
                 }
             ]
@@ -352,16 +503,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarOne".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                     serialized_name: "VAR_ONE".into(),
                 },
                 UnionVariant {
                     name: Some("VarTwo".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_TWO".into(),
                 }
             ]
@@ -378,16 +525,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarA".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarOne"))),
                     serialized_name: "VAR_A".into(),
                 },
                 UnionVariant {
                     name: Some("VarB".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_A".into(),
                 }
             ]
@@ -396,6 +539,28 @@ mod tests {
 ");
     }
 
+    #[test]
+    fn test_unnamed_union_variants() {
+        assert_snapshot!(TypeScriptType::Union(&Union {
+            name: "MyUnion".into(),
+            representation: UnionRepresentation::ExternallyTagged,
+            variants: vec![
+                UnionVariant {
+                    name: None,
+                    ty: Some(FieldType::Primitive(Primitive::String)),
+                    serialized_name: "text".into(),
+                },
+                UnionVariant {
+                    name: None,
+                    ty: Some(FieldType::List(Box::new(FieldType::Named(TypeRef::new("VarTwo"))))),
+                    serialized_name: "many-items".into(),
+                }
+            ]
+        })
+        .to_string(), @r###"export type MyUnion = { "text": string } | { "many-items": VarTwo[] };
+"###);
+    }
+
     #[test]
     fn test_untagged_option_union_output() {
         assert_snapshot!(TypeScriptType::Union(&Union {
@@ -404,16 +569,12 @@ mod tests {
             variants: vec![
                 UnionVariant {
                     name: Some("VarA".into()),
-                    ty: FieldType::Optional(Box::new(FieldType::Named(TypeRef {
-                        name: "VarOne".into()
-                    }))),
+                    ty: Some(FieldType::Optional(Box::new(FieldType::Named(TypeRef::new("VarOne"))))),
                     serialized_name: "VAR_A".into(),
                 },
                 UnionVariant {
                     name: Some("VarB".into()),
-                    ty: FieldType::Named(TypeRef {
-                        name: "VarTwo".into()
-                    }),
+                    ty: Some(FieldType::Named(TypeRef::new("VarTwo"))),
                     serialized_name: "VAR_A".into(),
                 }
             ]
@@ -425,7 +586,7 @@ mod tests {
     #[test]
     fn test_list_types() {
         assert_snapshot!(
-            FieldType::List(Box::new(FieldType::Primitive(Primitive::String))).typescript_type(),
+            FieldType::List(Box::new(FieldType::Primitive(Primitive::String))).typescript_type(&TypeScriptOptions::default()),
             @"string[]"
         );
     }
@@ -435,8 +596,8 @@ mod tests {
         assert_snapshot!(
             FieldType::Map{
                 key: Box::new(FieldType::Primitive(Primitive::String)),
-                value: Box::new(FieldType::Primitive(Primitive::Int))
-            }.typescript_type(),
+                value: Box::new(FieldType::Primitive(Primitive::Int(IntKind::I64)))
+            }.typescript_type(&TypeScriptOptions::default()),
             @"Record<string, number>"
         );
     }
@@ -444,7 +605,7 @@ mod tests {
     #[test]
     fn test_option_types() {
         assert_snapshot!(
-            FieldType::Optional(Box::new(FieldType::Primitive(Primitive::String))).typescript_type(),
+            FieldType::Optional(Box::new(FieldType::Primitive(Primitive::String))).typescript_type(&TypeScriptOptions::default()),
             @"string | null"
         );
     }
@@ -0,0 +1,172 @@
+/// Options for customising the generated typescript code.
+///
+/// Use with `registry_to_output_with_options::<TypeScriptType>`.
+#[derive(Clone, Debug, Default)]
+pub struct TypeScriptOptions {
+    pub(super) type_guards: bool,
+    pub(super) enum_style: TypeScriptEnumStyle,
+    pub(super) struct_style: TypeScriptStructStyle,
+    pub(super) optional_properties: bool,
+    pub(super) readonly_properties: bool,
+    pub(super) branded_newtypes: bool,
+    pub(super) wide_integers: TypeScriptWideIntegers,
+}
+
+/// How structs are declared in typescript
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TypeScriptStructStyle {
+    /// A type alias of an object type, e.g. `export type User = { name: string; }`
+    #[default]
+    TypeAlias,
+    /// An interface, e.g. `export interface User { name: string; }`
+    ///
+    /// Unlike type aliases these support declaration merging.
+    Interface,
+}
+
+/// How enums without data are represented in typescript
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TypeScriptEnumStyle {
+    /// A typescript `enum`, e.g. `export enum Status { Pending = "pending" }`
+    #[default]
+    Enum,
+    /// A union of string literals, e.g. `export type Status = "pending" | "done";`
+    ///
+    /// This also outputs the values as an `as const` array named `StatusValues` and an
+    /// `isStatus` type guard, which avoids runtime enums entirely.
+    StringUnion,
+}
+
+/// How integers that don't fit in a javascript number are represented in typescript.
+///
+/// Every number in javascript is an `f64`, so `JSON.parse` silently rounds any 64 or 128 bit
+/// integers above 2^53.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TypeScriptWideIntegers {
+    /// Output them as a `number`, which is fine if their values are known to be small
+    #[default]
+    Number,
+    /// Output them as a `number`, with a `// warning:` comment at the top of the output for
+    /// each type, field & variant that holds one
+    Warn,
+    /// Output them as a `bigint`.
+    ///
+    /// `JSON` won't handle these by itself, so this also outputs some functions to convert
+    /// them:
+    ///
+    /// - `reviveBigInts`, a reviver for `JSON.parse` that reads integers above 2^53 from the
+    ///   source text where the runtime supports it, so that no precision is lost.
+    /// - A `revive<Type>BigInts` function for every type, which converts the rest of the
+    ///   wide integers in a parsed value of that type, e.g.
+    ///   `reviveUserBigInts(JSON.parse(json, reviveBigInts))`.  Untagged unions can't tell
+    ///   which variant they hold, so they convert the integers of every variant.
+    /// - `replaceBigInts`, a replacer for `JSON.stringify` that writes bigints as numbers.
+    BigInt,
+    /// Output them as a `string`, for when the rust side serializes them as strings,
+    /// e.g. with serde_with's `DisplayFromStr`.
+    String,
+}
+
+impl TypeScriptOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Controls whether runtime type guards are output alongside each type.
+    ///
+    /// For a type `User` this outputs `isUser(value: unknown): value is User`, which checks
+    /// the shape of some JSON without any extra dependencies, and `assertUser`, which throws
+    /// a `TypeError` if the check fails.
+    pub fn with_type_guards(mut self, type_guards: bool) -> Self {
+        self.type_guards = type_guards;
+        self
+    }
+
+    /// Sets how enums without data are represented in typescript.
+    ///
+    /// Defaults to `TypeScriptEnumStyle::Enum`.
+    pub fn with_enum_style(mut self, style: TypeScriptEnumStyle) -> Self {
+        self.enum_style = style;
+        self
+    }
+
+    /// Sets how structs are declared in typescript.
+    ///
+    /// Defaults to `TypeScriptStructStyle::TypeAlias`.
+    pub fn with_struct_style(mut self, style: TypeScriptStructStyle) -> Self {
+        self.struct_style = style;
+        self
+    }
+
+    /// Controls whether fields that can be missing from the JSON are output as optional
+    /// properties.
+    ///
+    /// Fields with a `#[serde(skip_serializing_if = "...")]` become optional, e.g.
+    /// `name?: string`.  Options skipped with `Option::is_none` are never null, so these
+    /// lose their `| null`, but options skipped by any other function keep it, e.g.
+    /// `name?: string | null`.
+    pub fn with_optional_properties(mut self, optional_properties: bool) -> Self {
+        self.optional_properties = optional_properties;
+        self
+    }
+
+    /// Controls whether struct properties are marked as `readonly`.
+    pub fn with_readonly_properties(mut self, readonly_properties: bool) -> Self {
+        self.readonly_properties = readonly_properties;
+        self
+    }
+
+    /// Controls whether newtypes are output as branded types.
+    ///
+    /// By default `struct UserId(String)` becomes `type UserId = string`, so any string
+    /// can be used as a `UserId`.  With branding enabled this becomes
+    /// `type UserId = string & { readonly __brand: "UserId" }` along with a
+    /// `UserId(value: string): UserId` function to construct one.
+    pub fn with_branded_newtypes(mut self, branded_newtypes: bool) -> Self {
+        self.branded_newtypes = branded_newtypes;
+        self
+    }
+
+    /// Sets how 64 & 128 bit integers are represented in typescript.
+    ///
+    /// Defaults to `TypeScriptWideIntegers::Number`.
+    pub fn with_wide_integers(mut self, wide_integers: TypeScriptWideIntegers) -> Self {
+        self.wide_integers = wide_integers;
+        self
+    }
+}
+
+/// Options for customising the generated typescript API client.
+///
+/// Use with `registry_to_client::<TypeScriptClient>`.
+#[derive(Clone, Debug, Default)]
+pub struct TypeScriptClientOptions {
+    pub(super) types: TypeScriptOptions,
+    pub(super) types_module: Option<String>,
+}
+
+impl TypeScriptClientOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the options that the types were output with, so the client refers to them
+    /// in the same way.
+    ///
+    /// If the types use `TypeScriptWideIntegers::BigInt` the client converts bigints in
+    /// requests & responses with the functions that are output alongside the types.
+    pub fn with_type_options(mut self, types: TypeScriptOptions) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Imports the types from a module, e.g. `./types`.
+    ///
+    /// By default the client expects the types to be output into the same file.
+    pub fn with_types_module(mut self, module: impl Into<String>) -> Self {
+        self.types_module = Some(module.into());
+        self
+    }
+}
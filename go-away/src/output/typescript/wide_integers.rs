@@ -0,0 +1,329 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
+
+use indenter::indented;
+use indoc::writedoc;
+
+use super::{FieldType, IntKind, Primitive, TypeScriptType, UnionRepresentation};
+
+/// Finds everywhere in a set of types that holds integers too wide for a javascript number
+pub struct WideIntegers<'a> {
+    types: &'a [TypeScriptType<'a>],
+    /// The names of every type that holds a wide integer somewhere inside it
+    holders: HashSet<&'a str>,
+}
+
+impl<'a> WideIntegers<'a> {
+    pub fn new(types: &'a [TypeScriptType<'a>]) -> Self {
+        let named = types
+            .iter()
+            .map(|ty| (ty.name(), ty))
+            .collect::<HashMap<_, _>>();
+        let holders = types
+            .iter()
+            .map(TypeScriptType::name)
+            .filter(|name| holds_wide_int(&named, name, &mut Vec::new()))
+            .collect();
+
+        WideIntegers { types, holders }
+    }
+
+    /// A warning for every type, field & variant that directly holds a wide integer
+    pub fn warnings(&self) -> impl Iterator<Item = String> + '_ {
+        self.locations().filter_map(|(location, ty)| {
+            let kind = ty.widest_int().filter(|kind| !kind.fits_in_f64())?;
+            Some(format!(
+                "{location} holds {} bit integers, which lose precision in typescript when \
+                 they're above 2^53",
+                kind.bits()
+            ))
+        })
+    }
+
+    /// Iterates over the types of every field, newtype, alias & variant in `types`, along
+    /// with a description of where they are for warnings
+    fn locations(&self) -> impl Iterator<Item = (String, &'a FieldType)> + '_ {
+        self.types.iter().flat_map(|ty| {
+            let locations: Vec<(String, &'a FieldType)> = match ty {
+                TypeScriptType::Struct(details) => details
+                    .fields
+                    .iter()
+                    .map(|field| (format!("{}.{}", details.name, field.name), &field.ty))
+                    .collect(),
+                TypeScriptType::NewType(details) => vec![(details.name.clone(), &details.inner)],
+                TypeScriptType::Alias(details) => vec![(details.name.clone(), &details.inner)],
+                TypeScriptType::Enum(_) => vec![],
+                TypeScriptType::Union(details) => details
+                    .variants
+                    .iter()
+                    .filter_map(|variant| {
+                        let location = format!("{}::{}", details.name, variant.serialized_name);
+                        Some((location, variant.ty.as_ref()?))
+                    })
+                    .collect(),
+            };
+            locations
+        })
+    }
+
+    /// The reviver for a value of `ty`, if it holds any wide integers
+    fn reviver(&self, ty: &FieldType) -> Option<String> {
+        reviver(ty, &|name| self.holders.contains(name))
+    }
+}
+
+impl TypeScriptType<'_> {
+    fn name(&self) -> &str {
+        match self {
+            TypeScriptType::Struct(details) => &details.name,
+            TypeScriptType::NewType(details) => &details.name,
+            TypeScriptType::Alias(details) => &details.name,
+            TypeScriptType::Enum(details) => &details.name,
+            TypeScriptType::Union(details) => &details.name,
+        }
+    }
+}
+
+/// Whether the type called `name` holds a wide integer anywhere inside it, following any
+/// references to other types that haven't been `visited`
+fn holds_wide_int<'a>(
+    named: &HashMap<&str, &'a TypeScriptType<'a>>,
+    name: &'a str,
+    visited: &mut Vec<&'a str>,
+) -> bool {
+    if visited.contains(&name) {
+        return false;
+    }
+    visited.push(name);
+
+    let Some(ty) = named.get(name) else {
+        return false;
+    };
+    let mut inner: Box<dyn Iterator<Item = &'a FieldType>> = match ty {
+        TypeScriptType::Struct(details) => Box::new(details.fields.iter().map(|field| &field.ty)),
+        TypeScriptType::NewType(details) => Box::new(std::iter::once(&details.inner)),
+        TypeScriptType::Alias(details) => Box::new(std::iter::once(&details.inner)),
+        TypeScriptType::Enum(_) => Box::new(std::iter::empty()),
+        TypeScriptType::Union(details) => Box::new(
+            details
+                .variants
+                .iter()
+                .filter_map(|variant| variant.ty.as_ref()),
+        ),
+    };
+    inner.any(|ty| field_holds_wide_int(named, ty, visited))
+}
+
+fn field_holds_wide_int<'a>(
+    named: &HashMap<&str, &'a TypeScriptType<'a>>,
+    ty: &'a FieldType,
+    visited: &mut Vec<&'a str>,
+) -> bool {
+    match ty {
+        FieldType::Primitive(Primitive::Int(kind)) => !kind.fits_in_f64(),
+        FieldType::Primitive(_) => false,
+        FieldType::Optional(inner) | FieldType::List(inner) => {
+            field_holds_wide_int(named, inner, visited)
+        }
+        FieldType::Map { value, .. } => field_holds_wide_int(named, value, visited),
+        FieldType::Named(type_ref) => holds_wide_int(named, type_ref.name(), visited),
+    }
+}
+
+/// A typescript expression for a function that converts the wide integers held by a value
+/// of `ty` to bigints, or `None` if it doesn't hold any.
+///
+/// `revives` says whether the type with a given name has a reviver that does anything.
+pub fn reviver(ty: &FieldType, revives: &impl Fn(&str) -> bool) -> Option<String> {
+    match ty {
+        FieldType::Primitive(Primitive::Int(kind)) if !kind.fits_in_f64() => {
+            Some("toBigInt".to_string())
+        }
+        FieldType::Primitive(_) => None,
+        // Revivers pass `null` through as it is, so options don't need anything extra
+        FieldType::Optional(inner) => reviver(inner, revives),
+        FieldType::List(inner) => Some(format!("reviveList({})", reviver(inner, revives)?)),
+        // Map keys are always strings in JSON, so only the values need converting
+        FieldType::Map { value, .. } => Some(format!("reviveMap({})", reviver(value, revives)?)),
+        FieldType::Named(type_ref) => {
+            revives(type_ref.name()).then(|| reviver_name(type_ref.name()))
+        }
+    }
+}
+
+/// The name of the function that converts the wide integers in the type called `name`
+pub fn reviver_name(name: &str) -> String {
+    format!("revive{name}BigInts")
+}
+
+impl FieldType {
+    /// The widest integer that's directly held by this type.
+    ///
+    /// Map keys are ignored, as they're always strings in JSON.
+    fn widest_int(&self) -> Option<IntKind> {
+        match self {
+            FieldType::Primitive(Primitive::Int(kind)) => Some(*kind),
+            FieldType::Optional(inner) | FieldType::List(inner) => inner.widest_int(),
+            FieldType::Map { value, .. } => value.widest_int(),
+            FieldType::Named(_) | FieldType::Primitive(_) => None,
+        }
+    }
+}
+
+/// Writes out the functions for converting wide integers to & from bigints: a reviver &
+/// replacer for `JSON.parse` & `JSON.stringify`, and a `revive<Type>BigInts` function for
+/// every type.
+///
+/// Every type gets one, even if it has no wide integers, so that code referring to types by
+/// name (like the API client) can always use them.
+pub struct BigIntFunctions<'a>(pub &'a WideIntegers<'a>);
+
+impl fmt::Display for BigIntFunctions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wide_integers = self.0;
+        writedoc!(
+            f,
+            r#"
+                /**
+                 * A reviver for `JSON.parse` that reads integers too big for a `number` from the source
+                 * text as bigints, so that no precision is lost.  Runtimes that don't give revivers the
+                 * source text will already have rounded them.
+                 *
+                 * This can't tell which properties should hold bigints, so pass the result to the
+                 * `revive<Type>BigInts` function for the type being parsed to convert the rest, e.g.
+                 * `reviveUserBigInts(JSON.parse(json, reviveBigInts))`.
+                 */
+                export function reviveBigInts(key: string, value: unknown, context?: {{ source?: string }}): unknown {{
+                    const source = context?.source;
+                    if (typeof value === "number" && !Number.isSafeInteger(value) && source !== undefined && /^-?\d+$/.test(source)) {{
+                        return BigInt(source);
+                    }}
+                    return value;
+                }}
+
+                /**
+                 * A replacer for `JSON.stringify` that writes bigints as numbers, e.g.
+                 * `JSON.stringify(user, replaceBigInts)`.
+                 *
+                 * Runtimes without `JSON.rawJSON` can only write them as a `number`, which rounds any
+                 * above 2^53.
+                 */
+                export function replaceBigInts(key: string, value: unknown): unknown {{
+                    if (typeof value !== "bigint") {{
+                        return value;
+                    }}
+                    const rawJSON: ((text: string) => unknown) | undefined = (JSON as any).rawJSON;
+                    return rawJSON !== undefined ? rawJSON(value.toString()) : Number(value);
+                }}
+
+                /** Converts an integer to a bigint, leaving anything else as it is */
+                export function toBigInt(value: any): any {{
+                    return typeof value === "number" && Number.isInteger(value) ? BigInt(value) : value;
+                }}
+
+                /** Applies `revive` to every item of an array */
+                export function reviveList(revive: (value: any) => any): (value: any) => any {{
+                    return (value) => (Array.isArray(value) ? value.map((item) => revive(item)) : value);
+                }}
+
+                /** Applies `revive` to every value of an object */
+                export function reviveMap(revive: (value: any) => any): (value: any) => any {{
+                    return (value) =>
+                        typeof value === "object" && value !== null
+                            ? Object.fromEntries(Object.entries(value).map(([key, entry]) => [key, revive(entry)]))
+                            : value;
+                }}
+
+                function reviveProperty(value: any, property: string, revive: (value: any) => any) {{
+                    if (typeof value === "object" && value !== null && property in value) {{
+                        value[property] = revive(value[property]);
+                    }}
+                }}
+            "#
+        )?;
+
+        for ty in wide_integers.types {
+            let name = ty.name();
+            writeln!(f)?;
+            writeln!(
+                f,
+                "/** Converts the 64 & 128 bit integers in a parsed `{name}` to bigints */"
+            )?;
+            writeln!(
+                f,
+                "export function {}(value: any): {name} {{",
+                reviver_name(name)
+            )?;
+            write_reviver_body(wide_integers, ty, &mut indented(f))?;
+            writeln!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_reviver_body(
+    wide_integers: &WideIntegers<'_>,
+    ty: &TypeScriptType<'_>,
+    f: &mut dyn Write,
+) -> fmt::Result {
+    match ty {
+        TypeScriptType::Struct(details) => {
+            for field in &details.fields {
+                if let Some(reviver) = wide_integers.reviver(&field.ty) {
+                    writeln!(
+                        f,
+                        "reviveProperty(value, \"{}\", {reviver});",
+                        field.serialized_name
+                    )?;
+                }
+            }
+            writeln!(f, "return value;")
+        }
+        TypeScriptType::NewType(details) => {
+            write_inner_reviver(f, wide_integers.reviver(&details.inner))
+        }
+        TypeScriptType::Alias(details) => {
+            write_inner_reviver(f, wide_integers.reviver(&details.inner))
+        }
+        TypeScriptType::Enum(_) => writeln!(f, "return value;"),
+        TypeScriptType::Union(details) => {
+            let variants = details.variants.iter().filter_map(|variant| {
+                let reviver = wide_integers.reviver(variant.ty.as_ref()?)?;
+                Some((&variant.serialized_name, reviver))
+            });
+            for (serialized_name, reviver) in variants {
+                match &details.representation {
+                    UnionRepresentation::ExternallyTagged => writeln!(
+                        f,
+                        "reviveProperty(value, \"{serialized_name}\", {reviver});"
+                    )?,
+                    UnionRepresentation::InternallyTagged { tag } => {
+                        writeln!(f, "if (value?.[\"{tag}\"] === \"{serialized_name}\") {{")?;
+                        writeln!(indented(f), "return {reviver}(value);")?;
+                        writeln!(f, "}}")?;
+                    }
+                    UnionRepresentation::AdjacentlyTagged { tag, content } => {
+                        writeln!(f, "if (value?.[\"{tag}\"] === \"{serialized_name}\") {{")?;
+                        writeln!(
+                            indented(f),
+                            "reviveProperty(value, \"{content}\", {reviver});"
+                        )?;
+                        writeln!(f, "}}")?;
+                    }
+                    // There's no way to tell which variant this is, so every variant's
+                    // integers are converted
+                    UnionRepresentation::Untagged => writeln!(f, "value = {reviver}(value);")?,
+                }
+            }
+            writeln!(f, "return value;")
+        }
+    }
+}
+
+fn write_inner_reviver(f: &mut dyn Write, reviver: Option<String>) -> fmt::Result {
+    match reviver {
+        Some(reviver) => writeln!(f, "return {reviver}(value);"),
+        None => writeln!(f, "return value;"),
+    }
+}
@@ -0,0 +1,389 @@
+use std::{fmt, fmt::Write};
+
+use indenter::indented;
+
+use super::typescript::keywords::property_name;
+
+pub use crate::types::*;
+
+/// An enum representing the possible top-level types as zod schemas
+///
+/// This shouldn't be instaniated directly but passed using turbofish operator
+/// to registry_to_output enabling it to write out zod schemas.
+///
+/// Each type `Name` is output as a `NameSchema` constant along with a `Name` type that's
+/// inferred from the schema.  The output expects `z` to have been imported from `zod`.
+pub enum ZodSchema<'a> {
+    /// A struct variant
+    Struct(&'a Struct),
+    /// A new type variant
+    NewType(&'a NewType),
+    /// A type alias variant
+    Alias(&'a Alias),
+    /// A simple enum variant (does not contain data)
+    Enum(&'a Enum),
+    /// A union variant (enums with data)
+    Union(&'a Union),
+}
+
+impl fmt::Display for ZodSchema<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ZodSchema::Struct(details) => &details.name,
+            ZodSchema::NewType(details) => &details.name,
+            ZodSchema::Alias(details) => &details.name,
+            ZodSchema::Enum(details) => &details.name,
+            ZodSchema::Union(details) => &details.name,
+        };
+
+        // Each type starts with a blank line to separate it from the previous one,
+        // registry_to_output strips this from the start of the file.
+        write!(f, "\nexport const {} = ", schema_name(name))?;
+        match self {
+            ZodSchema::Struct(details) => write!(f, "{}", ObjectSchema(&details.fields))?,
+            ZodSchema::NewType(details) => write!(f, "{}", details.inner.zod_schema())?,
+            ZodSchema::Alias(details) => write!(f, "{}", details.inner.zod_schema())?,
+            ZodSchema::Enum(details) if details.variants.is_empty() => write!(f, "z.never()")?,
+            ZodSchema::Enum(details) => {
+                let values = details
+                    .variants
+                    .iter()
+                    .map(|variant| format!("\"{}\"", variant.serialized_name))
+                    .collect::<Vec<_>>();
+                write!(f, "z.enum([{}])", values.join(", "))?
+            }
+            ZodSchema::Union(details) => write!(f, "{}", UnionSchema(details))?,
+        }
+        writeln!(f, ";")?;
+        writeln!(
+            f,
+            "export type {name} = z.infer<typeof {}>;",
+            schema_name(name)
+        )
+    }
+}
+
+/// The name of the constant holding the schema for the type `name`
+fn schema_name(name: &str) -> String {
+    format!("{name}Schema")
+}
+
+/// A `z.object` with a property for each field
+struct ObjectSchema<'a>(&'a [Field]);
+
+impl fmt::Display for ObjectSchema<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "z.object({{}})");
+        }
+        writeln!(f, "z.object({{")?;
+        for field in self.0 {
+            let mut schema = field.ty.zod_schema();
+            // serde fills in missing options with `None` & missing defaulted fields with
+            // their default, so neither has to be present in the JSON.  Fields with a
+            // `skip_serializing_if` won't be present when serde skips them.
+            let may_be_missing = matches!(field.ty, FieldType::Optional(_))
+                || field.has_default
+                || field.skip_serializing_if.is_some();
+            if may_be_missing {
+                schema.push_str(".optional()");
+            }
+            writeln!(
+                indented(f),
+                "{}: {schema},",
+                property_name(&field.serialized_name)
+            )?;
+        }
+        write!(f, "}})")
+    }
+}
+
+struct UnionSchema<'a>(&'a Union);
+
+impl fmt::Display for UnionSchema<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let details = self.0;
+        match &details.representation {
+            UnionRepresentation::ExternallyTagged => {
+                let options = details.variants.iter().map(|variant| match &variant.ty {
+                    // Unit variants are serialized as a plain string
+                    None => format!("z.literal(\"{}\")", variant.serialized_name),
+                    Some(ty) => format!(
+                        "z.object({{ {}: {} }}).strict()",
+                        property_name(&variant.serialized_name),
+                        ty.zod_schema()
+                    ),
+                });
+                write!(f, "{}", union_call(options.collect()))
+            }
+            UnionRepresentation::InternallyTagged { tag } => {
+                let tag_property = |variant: &UnionVariant| {
+                    format!(
+                        "{{ {}: z.literal(\"{}\") }}",
+                        property_name(tag),
+                        variant.serialized_name
+                    )
+                };
+                let data_types = details
+                    .variants
+                    .iter()
+                    .filter_map(|variant| variant.ty.as_ref())
+                    .collect::<Vec<_>>();
+                if data_types.is_empty() {
+                    let options = details
+                        .variants
+                        .iter()
+                        .map(|variant| format!("z.object({})", tag_property(variant)));
+                    return write!(f, "{}", discriminated_union_call(tag, options.collect()));
+                }
+
+                // The data of each variant is merged into the object with the tag, which
+                // means calling methods on the other schemas.  These might not have been
+                // defined yet, so this all happens lazily.
+                //
+                // Only struct schemas are `z.object`s that can be extended with the tag &
+                // used in a `z.discriminatedUnion`.  Anything else is intersected with it.
+                let all_objects = data_types.iter().all(|ty| is_object_schema(ty));
+                let options = details
+                    .variants
+                    .iter()
+                    .map(|variant| match &variant.ty {
+                        None => format!("z.object({})", tag_property(variant)),
+                        Some(FieldType::Named(type_ref)) if all_objects => format!(
+                            "{}.extend({})",
+                            schema_name(type_ref.name()),
+                            tag_property(variant)
+                        ),
+                        Some(ty) => {
+                            format!(
+                                "z.intersection(z.object({}), {})",
+                                tag_property(variant),
+                                ty.zod_schema()
+                            )
+                        }
+                    })
+                    .collect();
+                let schema = match all_objects {
+                    true => discriminated_union_call(tag, options),
+                    false => union_call(options),
+                };
+                write!(f, "{}", Lazy(schema))
+            }
+            UnionRepresentation::AdjacentlyTagged { tag, content } => {
+                let options = details.variants.iter().map(|variant| {
+                    let tag = format!(
+                        "{}: z.literal(\"{}\")",
+                        property_name(tag),
+                        variant.serialized_name
+                    );
+                    match &variant.ty {
+                        None => format!("z.object({{ {tag} }})"),
+                        Some(ty) => format!(
+                            "z.object({{ {tag}, {}: {} }})",
+                            property_name(content),
+                            ty.zod_schema()
+                        ),
+                    }
+                });
+                write!(f, "{}", discriminated_union_call(tag, options.collect()))
+            }
+            UnionRepresentation::Untagged => {
+                let options = details.variants.iter().map(|variant| match &variant.ty {
+                    // Untagged unit variants are serialized as null
+                    None => "z.null()".to_string(),
+                    Some(ty) => ty.zod_schema(),
+                });
+                write!(f, "{}", union_call(options.collect()))
+            }
+        }
+    }
+}
+
+/// Whether the schema for `ty` is a `z.object` constant, which can be extended.
+///
+/// Every struct is output as one, even if it's recursive.
+fn is_object_schema(ty: &FieldType) -> bool {
+    matches!(ty, FieldType::Named(type_ref) if type_ref.is_struct())
+}
+
+/// A `z.union` of `options`.
+///
+/// zod needs at least two options for a union, so a single option is returned as is.
+fn union_call(options: Vec<String>) -> String {
+    match options.as_slice() {
+        [] => "z.never()".to_string(),
+        [option] => option.clone(),
+        options => list_call("z.union(", options),
+    }
+}
+
+/// A `z.discriminatedUnion` of `options` that switches on `tag`
+fn discriminated_union_call(tag: &str, options: Vec<String>) -> String {
+    match options.as_slice() {
+        [] => "z.never()".to_string(),
+        options => list_call(&format!("z.discriminatedUnion(\"{tag}\", "), options),
+    }
+}
+
+/// A call to `head` with an array of `items` as the last argument, one item per line
+fn list_call(head: &str, items: &[String]) -> String {
+    let mut output = format!("{head}[\n");
+    for item in items {
+        writeln!(indented(&mut output), "{item},").unwrap();
+    }
+    output.push_str("])");
+    output
+}
+
+/// Defers creating a schema until it's first used
+struct Lazy(String);
+
+impl fmt::Display for Lazy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "z.lazy(() =>")?;
+        writeln!(indented(f), "{}", self.0)?;
+        write!(f, ")")
+    }
+}
+
+impl FieldType {
+    fn zod_schema(&self) -> String {
+        match self {
+            // Schemas are constants, which can't be used before they're defined, so
+            // references to other types are resolved lazily.  typescript can't infer the
+            // type of a schema that refers to itself, so recursive references are `any`
+            FieldType::Named(type_ref) if type_ref.is_recursive() => {
+                format!(
+                    "z.lazy((): z.ZodTypeAny => {})",
+                    schema_name(type_ref.name())
+                )
+            }
+            FieldType::Named(type_ref) => {
+                format!("z.lazy(() => {})", schema_name(type_ref.name()))
+            }
+            FieldType::Optional(inner) => format!("{}.nullable()", inner.zod_schema()),
+            FieldType::List(inner) => format!("z.array({})", inner.zod_schema()),
+            FieldType::Map { key, value } => {
+                // JSON object keys are always strings, so only named types (e.g. enums)
+                // can usefully check them
+                let key = match key.as_ref() {
+                    FieldType::Named(_) => key.zod_schema(),
+                    _ => "z.string()".to_string(),
+                };
+                format!("z.record({key}, {})", value.zod_schema())
+            }
+            FieldType::Primitive(Primitive::String) => "z.string()".to_string(),
+            FieldType::Primitive(Primitive::Float) => "z.number()".to_string(),
+            FieldType::Primitive(Primitive::Int(_)) => "z.number().int()".to_string(),
+            FieldType::Primitive(Primitive::Bool) => "z.boolean()".to_string(),
+            FieldType::Primitive(Primitive::Time) => {
+                "z.string().datetime({ offset: true })".to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::types::{IntKind, TypeRef};
+
+    #[test]
+    fn test_struct_output() {
+        assert_snapshot!(
+            ZodSchema::Struct(&Struct {
+                name: "MyStruct".into(),
+                fields: vec![
+                    Field {
+                        name: "a_string".into(),
+                        serialized_name: "a_string".into(),
+                        ty: FieldType::Primitive(Primitive::String),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
+                    },
+                    Field {
+                        name: "an_int".into(),
+                        serialized_name: "renamed_tho".into(),
+                        ty: FieldType::Primitive(Primitive::Int(IntKind::I64)),
+                        go_tags: vec![],
+                        has_default: true,
+                        skip_serializing_if: None,
+                    },
+                    Field {
+                        name: "a_list".into(),
+                        serialized_name: "a_list".into(),
+                        ty: FieldType::List(Box::new(FieldType::Named(TypeRef::new("Other")))),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
+                    },
+                    Field {
+                        name: "an_option".into(),
+                        serialized_name: "an_option".into(),
+                        ty: FieldType::Optional(Box::new(FieldType::Primitive(Primitive::Float))),
+                        go_tags: vec![],
+                        has_default: false,
+                        skip_serializing_if: None,
+                    },
+                ],
+            })
+            .to_string(),
+            @r###"
+        export const MyStructSchema = z.object({
+            a_string: z.string(),
+            renamed_tho: z.number().int().optional(),
+            a_list: z.array(z.lazy(() => OtherSchema)),
+            an_option: z.number().nullable().optional(),
+        });
+        export type MyStruct = z.infer<typeof MyStructSchema>;
+        "###
+        );
+    }
+
+    #[test]
+    fn test_newtype_output() {
+        assert_snapshot!(ZodSchema::NewType(&NewType {
+            name: "UserId".into(),
+            inner: FieldType::Primitive(Primitive::String),
+        })
+        .to_string(), @r###"
+        export const UserIdSchema = z.string();
+        export type UserId = z.infer<typeof UserIdSchema>;
+        "###);
+    }
+
+    #[test]
+    fn test_enum_output() {
+        assert_snapshot!(ZodSchema::Enum(&Enum {
+            name: "FulfilmentType".into(),
+            variants: vec![
+                EnumVariant {
+                    name: "Delivery".into(),
+                    serialized_name: "DELIVERY".into(),
+                },
+                EnumVariant {
+                    name: "Collection".into(),
+                    serialized_name: "COLLECTION".into(),
+                },
+            ],
+        })
+        .to_string(), @r###"
+        export const FulfilmentTypeSchema = z.enum(["DELIVERY", "COLLECTION"]);
+        export type FulfilmentType = z.infer<typeof FulfilmentTypeSchema>;
+        "###);
+    }
+
+    #[test]
+    fn test_map_types() {
+        assert_snapshot!(
+            FieldType::Map{
+                key: Box::new(FieldType::Primitive(Primitive::Int(IntKind::I64))),
+                value: Box::new(FieldType::Primitive(Primitive::Bool))
+            }.zod_schema(),
+            @"z.record(z.string(), z.boolean())"
+        );
+    }
+}
@@ -1,7 +1,8 @@
-pub use std::collections::HashMap;
+pub use std::collections::{HashMap, HashSet};
 
 pub use super::{
-    types::{Alias, Enum, NewType, Struct, TypeRef, Union},
+    operation::OperationBuilder,
+    types::{Alias, Enum, HttpMethod, NewType, Operation, PathSegment, Struct, TypeRef, Union},
     TypeId,
 };
 
@@ -24,6 +25,17 @@ pub struct TypeRegistry {
     pub(super) newtypes: Vec<TypeId>,
     /// An ordered list of alias IDs
     pub(super) aliases: Vec<TypeId>,
+
+    /// The operations of an HTTP API that uses these types, in the order they
+    /// were registered.
+    pub(super) operations: Vec<Operation>,
+
+    /// The types that have been reserved but not yet registered, in the order
+    /// they were reserved.
+    in_progress: Vec<(TypeId, String)>,
+    /// The types that are part of a reference cycle, along with an ID for the cycle
+    /// they're in.  Types that refer to each other share a cycle ID.
+    cycles: HashMap<TypeId, usize>,
 }
 
 impl TypeRegistry {
@@ -32,6 +44,41 @@ impl TypeRegistry {
         TypeRegistry::default()
     }
 
+    /// Looks up a type that has already been registered or reserved.
+    ///
+    /// `TypeMetadata` impls should call this before visiting any of their contents,
+    /// and return the `TypeRef` if one is found.
+    pub fn lookup(&mut self, id: &TypeId) -> Option<TypeRef> {
+        if let Some(ty) = self.types.get(id) {
+            let mut type_ref = ty.type_ref();
+            type_ref.recursive = self.in_same_cycle_as_current(id);
+            return Some(type_ref);
+        }
+
+        let position = self.in_progress.iter().position(|(other, _)| other == id)?;
+
+        // We've found a reference back to a type that's still being registered,
+        // so everything registered since then is part of the same cycle.
+        let members = self.in_progress[position..]
+            .iter()
+            .map(|(other, _)| other.clone())
+            .collect::<Vec<_>>();
+        self.merge_cycle(members);
+
+        let mut type_ref = TypeRef::new(&self.in_progress[position].1);
+        type_ref.recursive = true;
+        Some(type_ref)
+    }
+
+    /// Reserves a name for a type before its contents are visited.
+    ///
+    /// This allows types to refer back to themselves (directly or indirectly)
+    /// without recursing forever.  The reservation is released when the type
+    /// is registered with one of the `register_` functions.
+    pub fn reserve(&mut self, id: TypeId, name: &str) {
+        self.in_progress.push((id, name.to_string()));
+    }
+
     /// Registers an `Alias` in the `TypeRegistry`
     ///
     /// Users should use `crate::TypeAlias::register_alias` rather than calling this directly.
@@ -82,19 +129,132 @@ impl TypeRegistry {
         self.register_type(id, Type::Union(details))
     }
 
+    /// Starts describing an operation in an HTTP API, e.g. `GET /users/{id}`.
+    ///
+    /// The types used by the operation are registered as it's built up, and the operation
+    /// itself is registered by calling `OperationBuilder::register`.
+    ///
+    /// ```rust
+    /// use go_away::{types::HttpMethod, TypeMetadata, TypeRegistry};
+    ///
+    /// #[derive(TypeMetadata)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// let mut registry = TypeRegistry::new();
+    /// registry
+    ///     .operation("get_user", HttpMethod::Get, "/users/{id}")
+    ///     .path_param::<u64>("id")
+    ///     .response::<User>()
+    ///     .register();
+    /// ```
+    pub fn operation(
+        &mut self,
+        name: &str,
+        method: HttpMethod,
+        path: &str,
+    ) -> OperationBuilder<'_> {
+        OperationBuilder::new(self, name, method, path)
+    }
+
+    /// Registers an `Operation`
+    ///
+    /// Users should usually use `TypeRegistry::operation` rather than calling this directly,
+    /// as that registers the types the operation uses as well.
+    ///
+    /// # Panics
+    ///
+    /// If an operation with the same name has already been registered, or the path
+    /// parameters don't match up with the parameters in the path.
+    pub fn register_operation(&mut self, operation: Operation) {
+        if self
+            .operations
+            .iter()
+            .any(|other| other.name == operation.name)
+        {
+            panic!("Operation {} was registered twice", operation.name);
+        }
+
+        let in_path = operation
+            .path_segments()
+            .into_iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Param(name) => Some(name),
+                PathSegment::Literal(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let declared = operation
+            .path_params
+            .iter()
+            .map(|param| param.name.as_str())
+            .collect::<Vec<_>>();
+        if in_path != declared {
+            panic!(
+                "Path params of operation {} don't match its path {}: {:?} vs {:?}",
+                operation.name, operation.path, declared, in_path
+            );
+        }
+
+        self.operations.push(operation);
+    }
+
     fn register_type(&mut self, id: TypeId, ty: Type) -> TypeRef {
+        self.in_progress.retain(|(other, _)| *other != id);
+
+        let recursive = self.in_same_cycle_as_current(&id);
         if self.types.contains_key(&id) {
             match self.types.get(&id) {
-                Some(existing) if ty.same_kind(existing) => return existing.type_ref(),
+                Some(existing) if ty.same_kind(existing) => {
+                    let mut type_ref = existing.type_ref();
+                    type_ref.recursive = recursive;
+                    return type_ref;
+                }
                 other => panic!("Type register mismatch: {:?} vs {:?}", ty, other),
             }
         }
 
-        let type_ref = ty.type_ref();
+        let mut type_ref = ty.type_ref();
+        type_ref.recursive = recursive;
         self.types.insert(id, ty);
 
         type_ref
     }
+
+    /// Whether `id` is in the same reference cycle as the type currently being
+    /// registered, i.e. a reference from that type to `id` is part of the cycle.
+    fn in_same_cycle_as_current(&self, id: &TypeId) -> bool {
+        let Some((current, _)) = self.in_progress.last() else {
+            return false;
+        };
+        match (self.cycles.get(id), self.cycles.get(current)) {
+            (Some(lhs), Some(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+
+    /// Records that all of `members` are part of one cycle, merging it with any cycles
+    /// they're already part of.
+    fn merge_cycle(&mut self, members: Vec<TypeId>) {
+        let existing = members
+            .iter()
+            .filter_map(|id| self.cycles.get(id).copied())
+            .collect::<HashSet<_>>();
+        let cycle = existing
+            .iter()
+            .min()
+            .copied()
+            .unwrap_or_else(|| self.cycles.values().max().map_or(0, |max| max + 1));
+
+        for other in self.cycles.values_mut() {
+            if existing.contains(other) {
+                *other = cycle;
+            }
+        }
+        for id in members {
+            self.cycles.insert(id, cycle);
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -108,26 +268,22 @@ pub enum Type {
 }
 
 impl Type {
-    fn type_ref(&self) -> TypeRef {
+    pub(crate) fn name(&self) -> &str {
         match self {
-            Type::Struct(st) => TypeRef {
-                name: st.name.clone(),
-            },
-            Type::Enum(en) => TypeRef {
-                name: en.name.clone(),
-            },
-            Type::Union(un) => TypeRef {
-                name: un.name.clone(),
-            },
-            Type::NewType(nt) => TypeRef {
-                name: nt.name.clone(),
-            },
-            Type::Alias(nt) => TypeRef {
-                name: nt.name.clone(),
-            },
+            Type::Struct(st) => &st.name,
+            Type::Enum(en) => &en.name,
+            Type::Union(un) => &un.name,
+            Type::NewType(nt) => &nt.name,
+            Type::Alias(nt) => &nt.name,
         }
     }
 
+    fn type_ref(&self) -> TypeRef {
+        let mut type_ref = TypeRef::new(self.name());
+        type_ref.is_struct = matches!(self, Type::Struct(_));
+        type_ref
+    }
+
     #[allow(clippy::match_like_matches_macro)]
     fn same_kind(&self, other: &Type) -> bool {
         match (self, other) {
@@ -4,7 +4,7 @@
 /// A struct.
 ///
 /// This will be serialized as a JSON object.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Struct {
     /// The name of the struct in Rust
     pub name: String,
@@ -14,7 +14,7 @@ pub struct Struct {
 }
 
 /// A field within a struct
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Field {
     /// The name of the field in rust.  If the field is un-named this will
     /// be a number.
@@ -24,12 +24,23 @@ pub struct Field {
     pub serialized_name: String,
     /// The type of the field
     pub ty: FieldType,
+    /// Extra struct tags to add to this field in go output, as `(key, value)` pairs.
+    ///
+    /// These come from `#[go_away(tag(key = "value"))]` attributes.
+    pub go_tags: Vec<(String, String)>,
+    /// Whether this field can be missing when deserializing because it has a
+    /// `#[serde(default)]`, either on the field itself or on its container.
+    pub has_default: bool,
+    /// The function from this fields `#[serde(skip_serializing_if = "...")]`, if it has
+    /// one, e.g. `Option::is_none`.  The field will be missing from the JSON whenever
+    /// this returns true.
+    pub skip_serializing_if: Option<String>,
 }
 
 /// A newtype struct (e.g. `struct SomeId(String)`)
 ///
 /// These are usually represented as their inner type when serialized.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NewType {
     /// The name of the struct in rust.
     pub name: String,
@@ -41,7 +52,7 @@ pub struct NewType {
 /// A type alias (e.g. `type SomeType = HashMap<String, String>;`)
 ///
 /// These are usually represented as their inner type when serialized.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Alias {
     /// The name of the type alias in rust.
     pub name: String,
@@ -53,7 +64,7 @@ pub struct Alias {
 /// An enum - note that in go-away these do not contain data.
 ///
 /// A Rust enum that's variants contain values will go to a `UnionType`
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Enum {
     /// The name of the enum
     pub name: String,
@@ -63,7 +74,7 @@ pub struct Enum {
 
 /// An enum variant - note that these are just names and are serialized
 /// as strings.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct EnumVariant {
     /// The name of the variant in code.
     pub name: String,
@@ -71,10 +82,10 @@ pub struct EnumVariant {
     pub serialized_name: String,
 }
 
-/// A union type - any rust enum that's variants contain data.
+/// A union type - any rust enum where at least one of the variants contains data.
 ///
 /// These will be serialzied differently depending on the UnionRepresentation.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Union {
     /// The name of the union
     pub name: String,
@@ -84,13 +95,13 @@ pub struct Union {
     pub variants: Vec<UnionVariant>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 /// A variant of a union type
 pub struct UnionVariant {
     /// The name of the variant if any
     pub name: Option<String>,
-    /// The type inside the variant
-    pub ty: FieldType,
+    /// The type inside the variant, or `None` for a unit variant
+    pub ty: Option<FieldType>,
     /// The name the variant will be serialized to
     pub serialized_name: String,
 }
@@ -119,7 +130,7 @@ pub enum UnionRepresentation {
 }
 
 /// The type of a field.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum FieldType {
     /// A `Option<T>` field
     Optional(Box<FieldType>),
@@ -139,30 +150,203 @@ pub enum FieldType {
 }
 
 /// The primitive types
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Primitive {
     /// Strings
     String,
     /// Floating point numbers
     Float,
     /// Integers
-    Int,
+    Int(IntKind),
     /// Booleans
     Bool,
     /// Time
     Time,
 }
 
+/// The kinds of integer, which determine the range of values an integer can hold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntKind {
+    /// An `i8`
+    I8,
+    /// An `i16`
+    I16,
+    /// An `i32`
+    I32,
+    /// An `i64`
+    I64,
+    /// An `i128`
+    I128,
+    /// A `u8`
+    U8,
+    /// A `u16`
+    U16,
+    /// A `u32`
+    U32,
+    /// A `u64`
+    U64,
+    /// A `u128`
+    U128,
+}
+
+impl IntKind {
+    /// The number of bits in this kind of integer
+    pub fn bits(self) -> u32 {
+        match self {
+            IntKind::I8 | IntKind::U8 => 8,
+            IntKind::I16 | IntKind::U16 => 16,
+            IntKind::I32 | IntKind::U32 => 32,
+            IntKind::I64 | IntKind::U64 => 64,
+            IntKind::I128 | IntKind::U128 => 128,
+        }
+    }
+
+    /// Whether this kind of integer can be negative
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntKind::I8 | IntKind::I16 | IntKind::I32 | IntKind::I64 | IntKind::I128
+        )
+    }
+
+    /// Whether every value of this kind of integer can be stored exactly in an `f64`.
+    ///
+    /// This isn't true for 64 bit integers & above, which matters for languages like
+    /// javascript where all numbers are `f64`s.
+    pub fn fits_in_f64(self) -> bool {
+        self.bits() <= 32
+    }
+}
+
 /// A reference to a given named type
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TypeRef {
     pub(crate) name: String,
+    /// Whether this reference is part of a reference cycle, i.e. the referenced type
+    /// contains the type this reference is in, either directly or via some other type.
+    pub(crate) recursive: bool,
+    /// Whether the referenced type is a `Struct`
+    pub(crate) is_struct: bool,
     // TODO: id: std::any::TypeId,
 }
 
 impl TypeRef {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        TypeRef {
+            name: name.into(),
+            recursive: false,
+            is_struct: false,
+        }
+    }
+
     /// Gets the name of the referenced type
     pub(crate) fn name(&self) -> &str {
         &self.name
     }
+
+    /// Whether this reference is part of a reference cycle.
+    ///
+    /// References to recursive types from types outside of their cycle aren't recursive.
+    ///
+    /// Some languages need these references to be boxed or behind a pointer.
+    pub(crate) fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Whether the referenced type is a `Struct`.
+    ///
+    /// References to types that are still being registered don't know what kind of
+    /// type they refer to, so this is false for them.
+    pub(crate) fn is_struct(&self) -> bool {
+        self.is_struct
+    }
+}
+
+/// An operation in an HTTP API, e.g. `GET /users/{id}`
+#[derive(Clone, Debug)]
+pub struct Operation {
+    /// The name of the operation, in snake case like a rust function, e.g. `get_user`
+    pub name: String,
+    /// The HTTP method of the operation
+    pub method: HttpMethod,
+    /// The path of the operation, with any parameters in braces, e.g. `/users/{id}`
+    pub path: String,
+    /// The parameters in the path, in the order they appear
+    pub path_params: Vec<PathParam>,
+    /// The type that's serialized into the query string, if any
+    pub query: Option<FieldType>,
+    /// The type of the JSON request body, if any
+    pub request: Option<FieldType>,
+    /// The type of the JSON response body for successful requests, if any
+    pub response: Option<FieldType>,
+    /// The type of the JSON response body for requests that fail, if any
+    pub error: Option<FieldType>,
+}
+
+/// A parameter in the path of an `Operation`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathParam {
+    /// The name of the parameter, as it appears in the path
+    pub name: String,
+    /// The type of the parameter
+    pub ty: FieldType,
+}
+
+/// The HTTP methods
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpMethod {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// `PUT`
+    Put,
+    /// `PATCH`
+    Patch,
+    /// `DELETE`
+    Delete,
+}
+
+impl HttpMethod {
+    /// The name of the method as it appears in a request, e.g. `GET`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+}
+
+impl Operation {
+    /// Splits `path` up into literal text & parameters
+    pub fn path_segments(&self) -> Vec<PathSegment<'_>> {
+        let mut segments = vec![];
+        let mut rest = self.path.as_str();
+        while let Some((literal, tail)) = rest.split_once('{') {
+            let Some((param, tail)) = tail.split_once('}') else {
+                break;
+            };
+            if !literal.is_empty() {
+                segments.push(PathSegment::Literal(literal));
+            }
+            segments.push(PathSegment::Param(param));
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            segments.push(PathSegment::Literal(rest));
+        }
+        segments
+    }
+}
+
+/// Part of the path of an `Operation`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// Some text that appears in the path as is
+    Literal(&'a str),
+    /// The name of a parameter that's substituted into the path
+    Param(&'a str),
 }
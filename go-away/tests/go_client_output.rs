@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use insta::assert_snapshot;
+
+use go_away::{types::HttpMethod, TypeMetadata, TypeRegistry};
+
+#[derive(TypeMetadata)]
+struct UserId(String);
+
+#[derive(TypeMetadata)]
+struct User {
+    id: UserId,
+    name: String,
+}
+
+#[derive(TypeMetadata)]
+struct NewUser {
+    name: String,
+}
+
+#[derive(TypeMetadata)]
+struct ListUsersQuery {
+    search: Option<String>,
+    page: u32,
+}
+
+#[derive(TypeMetadata)]
+struct ApiError {
+    message: String,
+}
+
+fn user_api() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("list_users", HttpMethod::Get, "/users")
+        .query::<ListUsersQuery>()
+        .response::<Vec<User>>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("create_user", HttpMethod::Post, "/users")
+        .request::<NewUser>()
+        .response::<User>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("get_user", HttpMethod::Get, "/users/{user_id}")
+        .path_param::<UserId>("user_id")
+        .response::<User>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation(
+            "delete_session",
+            HttpMethod::Delete,
+            "/users/{user_id}/sessions/{type}",
+        )
+        .path_param::<UserId>("user_id")
+        .path_param::<String>("type")
+        .register();
+    registry
+}
+
+#[test]
+fn client_output() {
+    let registry = user_api();
+
+    assert_snapshot!(go_away::registry_to_client::<go_away::GoClient>(
+        &registry,
+        &go_away::GoClientOptions::new()
+    ));
+}
+
+#[test]
+fn path_params_named_like_packages() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("get_link", HttpMethod::Get, "/links/{url}/{context}")
+        .path_param::<String>("url")
+        .path_param::<String>("context")
+        .register();
+
+    let output = go_away::registry_to_client::<go_away::GoClient>(
+        &registry,
+        &go_away::GoClientOptions::new(),
+    );
+
+    assert!(output.contains(
+        "func (c *Client) GetLink(ctx context.Context, url_ string, context_ string) error {\n\t\
+         path := \"/links/\" + url.PathEscape(fmt.Sprint(url_)) + \"/\" + url.PathEscape(fmt.Sprint(context_))\n"
+    ));
+}
+
+#[test]
+fn client_package() {
+    let registry = user_api();
+
+    let options = go_away::GoClientOptions::new().with_package("api");
+    let output = go_away::registry_to_client::<go_away::GoClient>(&registry, &options);
+
+    assert_snapshot!(output.split("// Client calls").next().unwrap(), @r###"
+    package api
+
+    import (
+    	"bytes"
+    	"context"
+    	"encoding/json"
+    	"fmt"
+    	"io"
+    	"net/http"
+    	"net/url"
+    	"strings"
+    )
+    "###);
+}
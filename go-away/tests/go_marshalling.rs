@@ -10,7 +10,9 @@ use std::{
 use indoc::writedoc;
 use serde::{Deserialize, Serialize};
 
-use go_away::{registry_to_output, TypeMetadata, TypeRegistry};
+use go_away::{
+    registry_to_output_with_options, GoOptions, GoUnionStyle, TypeMetadata, TypeRegistry,
+};
 
 #[cfg(feature = "chrono")]
 use chrono::DateTime;
@@ -116,13 +118,122 @@ fn test_struct_enum() {
     );
 }
 
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+enum MixedUnitEnum {
+    Nothing,
+    One(One),
+    Three { z: bool },
+}
+
+#[test]
+fn test_mixed_unit_enum() {
+    run_test(
+        "mixed_unit_enum",
+        "MixedUnitEnum",
+        &[
+            MixedUnitEnum::Nothing,
+            MixedUnitEnum::One(One { x: 1.0 }),
+            MixedUnitEnum::Three { z: true },
+        ],
+    );
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all_fields = "camelCase")]
+enum RenamedFieldsEnum {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn test_rename_all_fields() {
+    run_test(
+        "rename_all_fields",
+        "RenamedFieldsEnum",
+        &[
+            RenamedFieldsEnum::UserCreated {
+                user_id: "abcd".into(),
+            },
+            RenamedFieldsEnum::UserDeleted {
+                user_id: "abcd".into(),
+                deletion_reason: "spam".into(),
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_sealed_interface_unions() {
+    let options = GoOptions::new().with_union_style(GoUnionStyle::SealedInterface);
+    run_test_with_options(
+        "sealed_struct_enum",
+        "StructEnum",
+        &[
+            StructEnum::OptionOne {
+                x: "hello".into(),
+                y: 100,
+            },
+            StructEnum::OptionTwo {
+                foo: "bar".into(),
+                bar: Nested {
+                    a_string: "hello".into(),
+                    an_int: 65536,
+                    fulfilment_type: FulfilmentType::Collection,
+                },
+            },
+        ],
+        &options,
+    );
+    run_test_with_options(
+        "sealed_mixed_unit_enum",
+        "MixedUnitEnum",
+        &[
+            MixedUnitEnum::Nothing,
+            MixedUnitEnum::One(One { x: 1.0 }),
+            MixedUnitEnum::Three { z: true },
+        ],
+        &options,
+    );
+    run_test_with_options(
+        "sealed_pointer_enum",
+        "Node",
+        &[
+            Node::Leaf(Some("hello".into())),
+            Node::Branch(Box::new(Node::Leaf(Some("world".into())))),
+        ],
+        &options,
+    );
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "data")]
+enum Node {
+    Leaf(Option<String>),
+    Branch(Box<Node>),
+}
+
 fn run_test<T>(test_name: &str, type_name: &str, test_data: &[T])
+where
+    T: TypeMetadata + Serialize + serde::de::DeserializeOwned + PartialEq + Debug,
+{
+    run_test_with_options(test_name, type_name, test_data, &GoOptions::default())
+}
+
+fn run_test_with_options<T>(test_name: &str, type_name: &str, test_data: &[T], options: &GoOptions)
 where
     T: TypeMetadata + Serialize + serde::de::DeserializeOwned + PartialEq + Debug,
 {
     let mut registry = TypeRegistry::new();
     T::metadata(&mut registry);
-    let go_code = registry_to_output::<go_away::GoType>(&registry);
+    let go_code = registry_to_output_with_options::<go_away::GoType>(&registry, options);
+    assert_gofmt_formatted(&go_code);
     let path = format!("../go-temp/{}.go", test_name);
     let mut file = File::create(&path).unwrap();
 
@@ -210,3 +321,24 @@ where
         }
     }
 }
+
+/// Checks that gofmt wouldn't make any changes to `go_code`
+fn assert_gofmt_formatted(go_code: &str) {
+    let source = format!("package main\n\n{go_code}");
+    let mut process = Command::new("gofmt")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    process
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    let output = process.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), source);
+}
@@ -134,6 +134,364 @@ fn type_aliases() {
 
     MyType::register_alias("MyType", &mut registry);
 
-    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry), @"type MyType map[string]int
-");
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry), @r###"
+    type MyType map[string]int
+
+    func (v MyType) Validate() error {
+    	return validateMyType(v, "")
+    }
+
+    func validateMyType(n MyType, path string) error {
+    	return nil
+    }
+    "###);
+}
+
+#[derive(TypeMetadata)]
+struct Tree {
+    children: Vec<Tree>,
+    next_sibling: Option<Box<Tree>>,
+    parent: Box<Tree>,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum Expr {
+    Literal { value: i64 },
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+/// Refers to recursive types, but isn't part of their cycles
+#[derive(TypeMetadata)]
+struct Forest {
+    root: Tree,
+    trees: Vec<Tree>,
+    expr: Expr,
+}
+
+#[test]
+fn recursive_types() {
+    let mut registry = TypeRegistry::new();
+    Tree::metadata(&mut registry);
+    Expr::metadata(&mut registry);
+    Forest::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Failure {
+    reason: String,
+}
+
+#[derive(TypeMetadata)]
+enum ExternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum UntaggedStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[test]
+fn mixed_unit_and_data_variants() {
+    let mut registry = TypeRegistry::new();
+    ExternalStatus::metadata(&mut registry);
+    InternalStatus::metadata(&mut registry);
+    AdjacentStatus::metadata(&mut registry);
+    UntaggedStatus::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Event {
+    #[serde(with = "timestamp_seconds")]
+    #[go_away(as = "i64")]
+    created_at: std::time::SystemTime,
+}
+
+#[test]
+fn field_type_overrides() {
+    let mut registry = TypeRegistry::new();
+    Event::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry), @r###"
+    type Event struct {
+    	CreatedAt int `json:"created_at"`
+    }
+
+    func (v Event) Validate() error {
+    	return validateEvent(v, "")
+    }
+
+    func validateEvent(s Event, path string) error {
+    	return nil
+    }
+    "###);
+}
+
+#[derive(TypeMetadata)]
+#[serde(rename_all_fields = "camelCase")]
+enum AccountEvent {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn rename_all_fields() {
+    let mut registry = TypeRegistry::new();
+    AccountEvent::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[allow(non_snake_case)]
+struct Webhook {
+    user_id: String,
+    api_url: String,
+    callbackURL: String,
+    product_sku: String,
+    kind: WebhookKind,
+    target: WebhookTarget,
+}
+
+#[derive(TypeMetadata)]
+enum WebhookKind {
+    HttpPost,
+    JsonRpc,
+}
+
+#[derive(TypeMetadata)]
+enum WebhookTarget {
+    UserId(String),
+    Sku(String),
+}
+
+#[test]
+fn initialisms() {
+    let mut registry = TypeRegistry::new();
+    Webhook::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[test]
+fn custom_initialisms() {
+    let mut registry = TypeRegistry::new();
+    Webhook::metadata(&mut registry);
+
+    let options = go_away::GoOptions::new().with_extra_initialisms(["SKU"]);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Keywords {
+    r#type: String,
+    default: String,
+    func: String,
+    object: String,
+    r#in: String,
+    #[serde(rename = "content-type")]
+    content_type: String,
+}
+
+#[derive(TypeMetadata)]
+enum Instruction {
+    Validate(Keywords),
+    Default(Keywords),
+    Object(Keywords),
+}
+
+#[test]
+fn reserved_keywords() {
+    let mut registry = TypeRegistry::new();
+    Instruction::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+/// Variants that clash with the methods generated on unions
+#[derive(TypeMetadata)]
+enum Command {
+    Kind(Keywords),
+    Visit,
+    Stop(Keywords),
+    AsStop,
+}
+
+#[test]
+fn union_method_clashes() {
+    let mut registry = TypeRegistry::new();
+    Command::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[test]
+fn preserve_unknown_enum_values() {
+    let mut registry = TypeRegistry::new();
+    FulfilmentType::metadata(&mut registry);
+
+    let options = go_away::GoOptions::new().with_preserve_unknown_enum_values(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
+}
+
+#[derive(TypeMetadata)]
+enum Punctuation {
+    #[serde(rename = "say \"hi\"")]
+    Quote,
+    #[serde(rename = "C:\\")]
+    Backslash,
+    #[serde(rename = "`tick`")]
+    Backtick,
+}
+
+#[test]
+fn quoted_enum_values() {
+    let mut registry = TypeRegistry::new();
+    Punctuation::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Warehouse {
+    shelves: std::collections::HashMap<String, Vec<Option<FulfilmentType>>>,
+    default_shelf: Option<Vec<Nested>>,
+    label: String,
+}
+
+#[test]
+fn deep_validation() {
+    let mut registry = TypeRegistry::new();
+    Warehouse::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::GoType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum Shape {
+    Circle { radius: f64 },
+    Labelled(Nested),
+    Empty,
+}
+
+#[test]
+fn sealed_interface_unions() {
+    let mut registry = TypeRegistry::new();
+    Shape::metadata(&mut registry);
+
+    let options =
+        go_away::GoOptions::new().with_union_style(go_away::GoUnionStyle::SealedInterface);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum Node {
+    Leaf(Option<String>),
+    Branch(Box<Node>),
+}
+
+#[test]
+fn sealed_interface_pointer_variants() {
+    let mut registry = TypeRegistry::new();
+    Node::metadata(&mut registry);
+
+    let options =
+        go_away::GoOptions::new().with_union_style(go_away::GoUnionStyle::SealedInterface);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct ServiceConfig {
+    #[go_away(tag(validate = "hostname", db = "host_name"))]
+    host: String,
+    port: Option<u16>,
+    #[go_away(tag(yaml = "log-level"))]
+    log_level: String,
+}
+
+#[test]
+fn struct_tags() {
+    let mut registry = TypeRegistry::new();
+    ServiceConfig::metadata(&mut registry);
+
+    let options = go_away::GoOptions::new()
+        .with_tag_keys(["yaml", "mapstructure"])
+        .with_omit_empty(go_away::GoOmitEmpty::Optional)
+        .with_validate_required(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Alignment {
+    id: String,
+    display_name: Option<String>,
+    tags_by_category: std::collections::HashMap<String, Vec<String>>,
+    #[go_away(tag(validate = "min=1"))]
+    retries: i32,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "kind")]
+enum AlignedEvent {
+    Started,
+    AlignmentChanged(Alignment),
+}
+
+/// The expected output here has been checked against `gofmt`, which should leave it unchanged.
+#[test]
+fn gofmt_formatting() {
+    let mut registry = TypeRegistry::new();
+    AlignedEvent::metadata(&mut registry);
+
+    let options = go_away::GoOptions::new().with_tag_keys(["yaml"]);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<go_away::GoType>(
+        &registry, &options
+    ));
 }
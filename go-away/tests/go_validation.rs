@@ -29,29 +29,103 @@ struct Two {
 
 #[test]
 fn validate_with_no_variants_fails() {
-    assert!(run_test::<SomeUnion>(
-        "validate_with_no_variants_fails",
-        r#"SomeUnion {}"#
-    ))
+    assert_eq!(
+        run_test::<SomeUnion>("validate_with_no_variants_fails", r#"SomeUnion {}"#),
+        Outcome::Passed
+    )
 }
 
 #[test]
 fn validate_with_one_variant_passes() {
-    assert!(!run_test::<SomeUnion>(
-        "validate_with_one_variant_passes",
-        r#"SomeUnion {One: &One {X: 1.0}}"#,
-    ),);
+    // The go program expects marshalling to fail, so a union that marshals fine fails it
+    assert_eq!(
+        run_test::<SomeUnion>(
+            "validate_with_one_variant_passes",
+            r#"SomeUnion {One: &One {X: 1.0}}"#,
+        ),
+        Outcome::Failed
+    );
 }
 
 #[test]
 fn validate_with_two_variants_fails() {
-    assert!(run_test::<SomeUnion>(
-        "validate_with_two_variants_fails",
-        r#"SomeUnion {One: &One {X: 1.0}, Two: &Two {Y: true}}"#,
-    ),);
+    assert_eq!(
+        run_test::<SomeUnion>(
+            "validate_with_two_variants_fails",
+            r#"SomeUnion {One: &One {X: 1.0}, Two: &Two {Y: true}}"#,
+        ),
+        Outcome::Passed
+    );
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[test]
+fn unmarshal_known_enum_value_passes() {
+    assert_eq!(
+        run_unmarshal_test::<Colour>("unmarshal_known_enum_value_passes", "Colour", r#""Red""#),
+        Outcome::Passed
+    );
+}
+
+#[test]
+fn unmarshal_unknown_enum_value_fails() {
+    assert_eq!(
+        run_unmarshal_test::<Colour>("unmarshal_unknown_enum_value_fails", "Colour", r#""Blue""#),
+        Outcome::Failed
+    );
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+struct Basket {
+    orders: Vec<Order>,
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+struct Order {
+    payment: SomeUnion,
+    colour: Option<Colour>,
+}
+
+#[test]
+fn validate_reports_path_of_nested_union() {
+    assert_eq!(
+        run_validate_test::<Basket>(
+            "validate_reports_path_of_nested_union",
+            r#"Basket{Orders: []Order{{Payment: SomeUnion{One: &One{X: 1.0}}}, {Payment: SomeUnion{}}}}"#,
+            "orders[1].payment: one variant must be populated, found 0",
+        ),
+        Outcome::Passed
+    );
+}
+
+#[test]
+fn validate_reports_path_of_unknown_enum_value() {
+    assert_eq!(
+        run_validate_test::<Basket>(
+            "validate_reports_path_of_unknown_enum_value",
+            r#"Basket{Orders: []Order{{Payment: SomeUnion{Two: &Two{Y: true}}, Colour: func() *Colour { c := Colour("Blue"); return &c }()}}}"#,
+            r#"orders[0].colour: unknown Colour value: "Blue""#,
+        ),
+        Outcome::Passed
+    );
+}
+
+/// The result of running one of the go programs below
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// The program compiled & exited successfully
+    Passed,
+    /// The program compiled but failed one of its checks, exiting via `log.Fatalf`
+    Failed,
 }
 
-fn run_test<T>(test_name: &str, data: &str) -> bool
+/// Runs some go that marshals `data`, which passes if marshalling fails
+fn run_test<T>(test_name: &str, data: &str) -> Outcome
 where
     T: TypeMetadata + Serialize + serde::de::DeserializeOwned + PartialEq + Debug,
 {
@@ -93,17 +167,122 @@ where
     )
     .unwrap();
 
-    let process = Command::new("go")
-        .args(["run", &path])
+    run_go(&path)
+}
+
+/// Runs some go that unmarshals `json` into `type_name`
+fn run_unmarshal_test<T>(test_name: &str, type_name: &str, json: &str) -> Outcome
+where
+    T: TypeMetadata,
+{
+    let mut registry = TypeRegistry::new();
+    T::metadata(&mut registry);
+    let go_code = registry_to_output::<go_away::GoType>(&registry);
+    let path = format!("../go-temp/{}.go", test_name);
+    let mut file = File::create(&path).unwrap();
+
+    writedoc!(
+        &mut file,
+        r#"
+		package main
+
+		import (
+			"encoding/json"
+			"fmt"
+			"log"
+		)
+
+		{}
+
+		func main() {{
+			var output {}
+			if err := json.Unmarshal([]byte(`{}`), &output); err != nil {{
+				log.Fatalf("Failed to unmarshal: %v", err)
+			}}
+		}}
+		"#,
+        go_code,
+        type_name,
+        json
+    )
+    .unwrap();
+
+    run_go(&path)
+}
+
+/// Runs some go that validates `data`, which passes if it fails with `expected_error`
+fn run_validate_test<T>(test_name: &str, data: &str, expected_error: &str) -> Outcome
+where
+    T: TypeMetadata,
+{
+    let mut registry = TypeRegistry::new();
+    T::metadata(&mut registry);
+    let go_code = registry_to_output::<go_away::GoType>(&registry);
+    let path = format!("../go-temp/{}.go", test_name);
+    let mut file = File::create(&path).unwrap();
+
+    writedoc!(
+        &mut file,
+        r#"
+		package main
+
+		import (
+			"encoding/json"
+			"errors"
+			"fmt"
+			"log"
+		)
+
+		{}
+
+		func main() {{
+			err := ({}).Validate()
+			if err == nil {{
+				log.Fatalf("Expected an error, did not get one")
+			}}
+			if err.Error() != `{}` {{
+				log.Fatalf("Unexpected error: %v", err)
+			}}
+		}}
+		"#,
+        go_code,
+        data,
+        expected_error
+    )
+    .unwrap();
+
+    run_go(&path)
+}
+
+/// Compiles & runs the go program at `path`.
+///
+/// Panics if it doesn't compile or exits in some way other than `log.Fatalf`, so a
+/// broken program is never mistaken for one that failed its checks.
+fn run_go(path: &str) -> Outcome {
+    let binary = format!("{}.bin", path.trim_end_matches(".go"));
+    let build = Command::new("go")
+        .args(["build", "-o", &binary, path])
+        .output()
+        .unwrap();
+    if !build.status.success() {
+        panic!(
+            "Failed to compile {path}:\n{}",
+            String::from_utf8_lossy(&build.stderr)
+        );
+    }
+
+    let output = Command::new(&binary)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .spawn()
+        .output()
         .unwrap();
-
-    // Uncomment if you wish to output the result of the process
-    // println!("{}", String::from_utf8_lossy(&output.stdout));
-    // println!("{}", String::from_utf8_lossy(&output.stderr));
-    let output = process.wait_with_output().unwrap();
-
-    output.status.success()
+    match output.status.code() {
+        Some(0) => Outcome::Passed,
+        Some(1) => Outcome::Failed,
+        _ => panic!(
+            "{path} exited unexpectedly ({}):\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    }
 }
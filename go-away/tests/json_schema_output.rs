@@ -0,0 +1,139 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use insta::assert_snapshot;
+
+use go_away::{JsonSchemaOptions, TypeAlias, TypeMetadata, TypeRegistry};
+
+#[derive(TypeMetadata)]
+struct Reading {
+    sensor: String,
+    value: f64,
+    active: bool,
+    sequence: u8,
+    offset: i128,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    limits: HashMap<Level, i32>,
+}
+
+#[derive(TypeMetadata)]
+enum Level {
+    Low,
+    High,
+}
+
+#[test]
+fn field_types() {
+    let mut registry = TypeRegistry::new();
+    Reading::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_json_schema(
+        &registry,
+        &JsonSchemaOptions::new()
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Card {
+    last_four: String,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum Internal {
+    Cash,
+    Card(Card),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "kind", content = "data")]
+enum Adjacent {
+    Cash,
+    Card(Card),
+}
+
+#[derive(TypeMetadata)]
+enum External {
+    Cash,
+    Card(Card),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum Untagged {
+    Cash,
+    Card(Card),
+}
+
+#[test]
+fn unions() {
+    let mut registry = TypeRegistry::new();
+    Internal::metadata(&mut registry);
+    Adjacent::metadata(&mut registry);
+    External::metadata(&mut registry);
+    Untagged::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_json_schema(
+        &registry,
+        &JsonSchemaOptions::new()
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct SensorId(String);
+
+#[test]
+fn root_and_id() {
+    type Sensors = HashMap<String, SensorId>;
+
+    let mut registry = TypeRegistry::new();
+    Sensors::register_alias("Sensors", &mut registry);
+
+    assert_snapshot!(go_away::registry_to_json_schema(
+        &registry,
+        &JsonSchemaOptions::new()
+            .with_id("https://example.com/sensors.json")
+            .with_root("Sensors")
+    ), @r###"
+    {
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "$id": "https://example.com/sensors.json",
+      "$ref": "#/$defs/Sensors",
+      "$defs": {
+        "SensorId": {
+          "type": "string"
+        },
+        "Sensors": {
+          "type": "object",
+          "additionalProperties": {
+            "$ref": "#/$defs/SensorId"
+          }
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn single_type() {
+    let mut registry = TypeRegistry::new();
+    Card::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::JsonSchemaType>(&registry), @r###"
+    {
+      "type": "object",
+      "properties": {
+        "last_four": {
+          "type": "string"
+        }
+      },
+      "required": [
+        "last_four"
+      ]
+    }
+    "###);
+}
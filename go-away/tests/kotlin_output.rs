@@ -159,3 +159,76 @@ fn type_aliases() {
     typealias MyType = Map<String, Long>
     "###);
 }
+
+#[derive(TypeMetadata)]
+struct Failure {
+    reason: String,
+}
+
+#[derive(TypeMetadata)]
+enum Status {
+    Pending,
+    Failed(Failure),
+}
+
+#[test]
+fn mixed_unit_and_data_variants() {
+    let mut registry = TypeRegistry::new();
+    Status::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::KotlinType>(
+        &registry
+    ));
+}
+
+#[derive(TypeMetadata)]
+#[serde(rename_all_fields = "camelCase")]
+enum AccountEvent {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn rename_all_fields() {
+    let mut registry = TypeRegistry::new();
+    AccountEvent::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::KotlinType>(
+        &registry
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Keywords {
+    r#type: String,
+    default: String,
+    func: String,
+    object: String,
+    r#in: String,
+    #[serde(rename = "content-type")]
+    content_type: String,
+}
+
+#[derive(TypeMetadata)]
+enum Instruction {
+    Validate(Keywords),
+    Default(Keywords),
+    Object(Keywords),
+}
+
+#[test]
+fn reserved_keywords() {
+    let mut registry = TypeRegistry::new();
+    Instruction::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::KotlinType>(
+        &registry
+    ));
+}
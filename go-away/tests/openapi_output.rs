@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use insta::assert_snapshot;
+
+use go_away::{types::HttpMethod, OpenApiFormat, OpenApiOptions, TypeMetadata, TypeRegistry};
+
+#[derive(TypeMetadata)]
+struct UserId(u64);
+
+#[derive(TypeMetadata)]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(TypeMetadata)]
+struct User {
+    id: UserId,
+    name: String,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    permissions: HashMap<Role, bool>,
+}
+
+#[derive(TypeMetadata)]
+struct Card {
+    last_four: String,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum Payment {
+    Cash,
+    Card(Card),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "kind", content = "data")]
+enum Event {
+    Joined(User),
+    Left,
+}
+
+#[derive(TypeMetadata)]
+enum Status {
+    Unknown,
+    Active(User),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum Lookup {
+    Missing,
+    Found(User),
+}
+
+#[test]
+fn schemas() {
+    let mut registry = TypeRegistry::new();
+    User::metadata(&mut registry);
+    Payment::metadata(&mut registry);
+    Event::metadata(&mut registry);
+    Status::metadata(&mut registry);
+    Lookup::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_openapi(
+        &registry,
+        &OpenApiOptions::new("Users", "1.0.0")
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct RefundCard {
+    last_four: String,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum Refund {
+    Cash,
+    Card(RefundCard),
+}
+
+#[test]
+fn variant_schemas_named_like_types() {
+    let mut registry = TypeRegistry::new();
+    Refund::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_openapi(
+        &registry,
+        &OpenApiOptions::new("Refunds", "1.0.0")
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct ListUsersQuery {
+    search: Option<String>,
+    page: u32,
+}
+
+#[derive(TypeMetadata)]
+struct NewUser {
+    name: String,
+}
+
+#[derive(TypeMetadata)]
+struct ApiError {
+    message: String,
+}
+
+fn user_api() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("list_users", HttpMethod::Get, "/users")
+        .query::<ListUsersQuery>()
+        .response::<Vec<NewUser>>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("create_user", HttpMethod::Post, "/users")
+        .request::<NewUser>()
+        .response::<NewUser>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("delete_user", HttpMethod::Delete, "/users/{id}")
+        .path_param::<u64>("id")
+        .register();
+    registry
+}
+
+#[test]
+fn paths() {
+    let registry = user_api();
+
+    assert_snapshot!(go_away::registry_to_openapi(
+        &registry,
+        &OpenApiOptions::new("Users", "1.0.0")
+    ));
+}
+
+#[test]
+fn yaml_output() {
+    let registry = user_api();
+
+    assert_snapshot!(go_away::registry_to_openapi(
+        &registry,
+        &OpenApiOptions::new("Users", "1.0.0").with_format(OpenApiFormat::Yaml)
+    ));
+}
@@ -22,6 +22,7 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 
 */
 
+#[allow(dead_code)]
 #[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
 enum InternallyTaggedTupleEnum {
@@ -170,6 +171,57 @@ fn test_struct_enum() {
     );
 }
 
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+enum MixedUnitEnum {
+    Nothing,
+    One(One),
+    Three { z: bool },
+}
+
+#[test]
+fn test_mixed_unit_enum() {
+    run_test(
+        "mixed_unit_enum",
+        "MixedUnitEnum",
+        &[
+            MixedUnitEnum::Nothing,
+            MixedUnitEnum::One(One { x: 1.0 }),
+            MixedUnitEnum::Three { z: true },
+        ],
+    );
+}
+
+#[derive(TypeMetadata, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all_fields = "camelCase")]
+enum RenamedFieldsEnum {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn test_rename_all_fields() {
+    run_test(
+        "rename_all_fields",
+        "RenamedFieldsEnum",
+        &[
+            RenamedFieldsEnum::UserCreated {
+                user_id: "abcd".into(),
+            },
+            RenamedFieldsEnum::UserDeleted {
+                user_id: "abcd".into(),
+                deletion_reason: "spam".into(),
+            },
+        ],
+    );
+}
+
 fn run_test<T>(test_name: &str, type_name: &str, test_data: &[T])
 where
     T: TypeMetadata + Serialize + serde::de::DeserializeOwned + PartialEq + Debug,
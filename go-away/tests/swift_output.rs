@@ -148,3 +148,116 @@ fn type_aliases() {
     typealias MyType = [String: Int64]
     "###);
 }
+
+#[derive(TypeMetadata)]
+struct Tree {
+    children: Vec<Tree>,
+    next_sibling: Option<Box<Tree>>,
+    parent: Box<Tree>,
+}
+
+#[derive(TypeMetadata)]
+enum Expr {
+    Literal { value: i64 },
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+#[test]
+fn recursive_types() {
+    let mut registry = TypeRegistry::new();
+    Tree::metadata(&mut registry);
+    Expr::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::SwiftType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Failure {
+    reason: String,
+}
+
+#[derive(TypeMetadata)]
+enum ExternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum UntaggedStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[test]
+fn mixed_unit_and_data_variants() {
+    let mut registry = TypeRegistry::new();
+    ExternalStatus::metadata(&mut registry);
+    InternalStatus::metadata(&mut registry);
+    AdjacentStatus::metadata(&mut registry);
+    UntaggedStatus::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::SwiftType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[serde(rename_all_fields = "camelCase")]
+enum AccountEvent {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn rename_all_fields() {
+    let mut registry = TypeRegistry::new();
+    AccountEvent::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::SwiftType>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Keywords {
+    r#type: String,
+    default: String,
+    func: String,
+    object: String,
+    r#in: String,
+    #[serde(rename = "content-type")]
+    content_type: String,
+}
+
+#[derive(TypeMetadata)]
+enum Instruction {
+    Validate(Keywords),
+    Default(Keywords),
+    Object(Keywords),
+}
+
+#[test]
+fn reserved_keywords() {
+    let mut registry = TypeRegistry::new();
+    Instruction::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::SwiftType>(&registry));
+}
@@ -0,0 +1,214 @@
+#![allow(dead_code)]
+
+use insta::assert_snapshot;
+
+use go_away::{types::HttpMethod, TypeMetadata, TypeRegistry};
+
+#[derive(TypeMetadata)]
+struct UserId(String);
+
+#[derive(TypeMetadata)]
+struct User {
+    id: UserId,
+    name: String,
+}
+
+#[derive(TypeMetadata)]
+struct NewUser {
+    name: String,
+}
+
+#[derive(TypeMetadata)]
+struct ListUsersQuery {
+    search: Option<String>,
+    page: u32,
+}
+
+#[derive(TypeMetadata)]
+struct ApiError {
+    message: String,
+}
+
+fn user_api() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("list_users", HttpMethod::Get, "/users")
+        .query::<ListUsersQuery>()
+        .response::<Vec<User>>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("create_user", HttpMethod::Post, "/users")
+        .request::<NewUser>()
+        .response::<User>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("get_user", HttpMethod::Get, "/users/{user_id}")
+        .path_param::<UserId>("user_id")
+        .response::<User>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation(
+            "delete",
+            HttpMethod::Delete,
+            "/users/{user_id}/sessions/{id}",
+        )
+        .path_param::<UserId>("user_id")
+        .path_param::<u64>("id")
+        .register();
+    registry
+}
+
+#[test]
+fn client_output() {
+    let registry = user_api();
+
+    assert_snapshot!(go_away::registry_to_client::<go_away::TypeScriptClient>(
+        &registry,
+        &go_away::TypeScriptClientOptions::new()
+    ));
+}
+
+#[test]
+fn client_types_module() {
+    let registry = user_api();
+
+    let options = go_away::TypeScriptClientOptions::new().with_types_module("./types");
+    let output = go_away::registry_to_client::<go_away::TypeScriptClient>(&registry, &options);
+
+    assert!(output.starts_with(
+        "import type { ApiError, ListUsersQuery, NewUser, User, UserId } from \"./types\";\n\n"
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Account {
+    id: u64,
+    name: String,
+}
+
+#[test]
+fn bigint_client() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("list_accounts", HttpMethod::Get, "/accounts")
+        .response::<Vec<Account>>()
+        .error::<ApiError>()
+        .register();
+    registry
+        .operation("create_account", HttpMethod::Post, "/accounts")
+        .request::<Account>()
+        .response::<Account>()
+        .register();
+
+    let options = go_away::TypeScriptClientOptions::new()
+        .with_type_options(
+            go_away::TypeScriptOptions::new()
+                .with_wide_integers(go_away::TypeScriptWideIntegers::BigInt),
+        )
+        .with_types_module("./types");
+
+    assert_snapshot!(go_away::registry_to_client::<go_away::TypeScriptClient>(
+        &registry, &options
+    ));
+}
+
+#[test]
+fn types_used_by_operations_are_registered() {
+    let registry = user_api();
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(&registry), @r###"
+    export type NewUser = {
+        name: string;
+    }
+    export type ApiError = {
+        message: string;
+    }
+    export type User = {
+        id: UserId;
+        name: string;
+    }
+    export type ListUsersQuery = {
+        search: string | null;
+        page: number;
+    }
+    export type UserId = string;
+    "###);
+}
+
+#[test]
+fn path_params_named_like_locals() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("get_config", HttpMethod::Get, "/configs/{config}/{body}")
+        .path_param::<String>("config")
+        .path_param::<String>("body")
+        .query::<ListUsersQuery>()
+        .register();
+
+    let output = go_away::registry_to_client::<go_away::TypeScriptClient>(
+        &registry,
+        &go_away::TypeScriptClientOptions::new(),
+    );
+
+    assert!(output.contains(
+        "export function getConfig(config: ClientConfig, config_: string, body_: string, query: ListUsersQuery): Promise<void> {\n    \
+         return request<void, unknown>(config, \"GET\", `/configs/${encodeURIComponent(String(config_))}/${encodeURIComponent(String(body_))}`, { query });\n}"
+    ));
+}
+
+#[test]
+fn operations_named_like_module_names() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("request", HttpMethod::Post, "/requests")
+        .register();
+    registry
+        .operation("fetch", HttpMethod::Get, "/fetches")
+        .register();
+
+    let output = go_away::registry_to_client::<go_away::TypeScriptClient>(
+        &registry,
+        &go_away::TypeScriptClientOptions::new(),
+    );
+
+    assert!(output.contains("export function request_(config: ClientConfig): Promise<void> {"));
+    assert!(output.contains("export function fetch_(config: ClientConfig): Promise<void> {"));
+    assert!(output.contains("async function request<T, E>("));
+}
+
+#[test]
+fn paths_are_escaped() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("quoted", HttpMethod::Get, r#"/say "hi"\now"#)
+        .register();
+    registry
+        .operation("templated", HttpMethod::Get, r"/`{id}`/a\b/$")
+        .path_param::<String>("id")
+        .register();
+
+    let output = go_away::registry_to_client::<go_away::TypeScriptClient>(
+        &registry,
+        &go_away::TypeScriptClientOptions::new(),
+    );
+
+    assert!(
+        output.contains(r#"return request<void, unknown>(config, "GET", "/say \"hi\"\\now", {});"#)
+    );
+    assert!(output.contains(
+        r#"return request<void, unknown>(config, "GET", `/\`${encodeURIComponent(String(id))}\`/a\\b/$`, {});"#
+    ));
+}
+
+#[test]
+#[should_panic(expected = "don't match its path")]
+fn path_params_must_match_the_path() {
+    let mut registry = TypeRegistry::new();
+    registry
+        .operation("get_user", HttpMethod::Get, "/users/{user_id}")
+        .path_param::<UserId>("id")
+        .register();
+}
@@ -186,3 +186,504 @@ fn type_aliases() {
         &registry
     ));
 }
+
+#[cfg(feature = "all")]
+#[derive(TypeMetadata)]
+struct ThirdPartyTypes {
+    id: uuid::Uuid,
+    homepage: url::Url,
+    version: semver::Version,
+    payload: bytes::Bytes,
+    ordered: indexmap::IndexMap<String, i64>,
+    small: smallvec::SmallVec<[bool; 4]>,
+    score: ordered_float::OrderedFloat<f64>,
+}
+
+#[cfg(feature = "all")]
+#[test]
+fn third_party_types() {
+    let mut registry = TypeRegistry::new();
+    ThirdPartyTypes::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ), @r###"
+    export type ThirdPartyTypes = {
+        id: string;
+        homepage: string;
+        version: string;
+        payload: number[];
+        ordered: Record<string, number>;
+        small: boolean[];
+        score: number;
+    }
+    "###);
+}
+
+#[derive(TypeMetadata)]
+struct Failure {
+    reason: String,
+}
+
+#[derive(TypeMetadata)]
+enum ExternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum UntaggedStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalUnits {
+    Pending,
+    Done,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentUnits {
+    Pending,
+    Done,
+}
+
+#[test]
+fn tagged_unit_variants() {
+    let mut registry = TypeRegistry::new();
+    InternalUnits::metadata(&mut registry);
+    AdjacentUnits::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(&registry), @r###"
+    export type AdjacentUnits = { "type": "Pending" } | { "type": "Done" };
+    export type InternalUnits = { "type": "Pending" } | { "type": "Done" };
+    "###);
+}
+
+#[test]
+fn mixed_unit_and_data_variants() {
+    let mut registry = TypeRegistry::new();
+    ExternalStatus::metadata(&mut registry);
+    InternalStatus::metadata(&mut registry);
+    AdjacentStatus::metadata(&mut registry);
+    UntaggedStatus::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ));
+}
+
+#[derive(TypeMetadata)]
+#[serde(transparent)]
+struct UserId {
+    id: i64,
+}
+
+#[derive(TypeMetadata)]
+#[serde(try_from = "String", into = "String")]
+struct Email {
+    user: String,
+    domain: String,
+}
+
+#[derive(TypeMetadata)]
+struct Account {
+    id: UserId,
+    email: Email,
+}
+
+#[test]
+fn transparent_and_proxy_types() {
+    let mut registry = TypeRegistry::new();
+    Account::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ), @r###"
+    export type Account = {
+        id: UserId;
+        email: Email;
+    }
+    export type Email = string;
+    export type UserId = number;
+    "###);
+}
+
+#[derive(TypeMetadata)]
+#[serde(rename_all_fields = "camelCase")]
+enum AccountEvent {
+    UserCreated {
+        user_id: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted {
+        user_id: String,
+        #[serde(rename = "why")]
+        deletion_reason: String,
+    },
+}
+
+#[test]
+fn rename_all_fields() {
+    let mut registry = TypeRegistry::new();
+    AccountEvent::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Keywords {
+    r#type: String,
+    default: String,
+    func: String,
+    object: String,
+    r#in: String,
+    #[serde(rename = "content-type")]
+    content_type: String,
+}
+
+#[derive(TypeMetadata)]
+enum Instruction {
+    Validate(Keywords),
+    Default(Keywords),
+    Object(Keywords),
+}
+
+#[test]
+fn reserved_keywords() {
+    let mut registry = TypeRegistry::new();
+    Instruction::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ));
+}
+
+#[derive(TypeMetadata)]
+struct Inventory {
+    name: String,
+    count: i32,
+    price: Option<f64>,
+    tags: Vec<Vec<String>>,
+    stock: std::collections::HashMap<String, Vec<Nested>>,
+}
+
+#[test]
+fn type_guards() {
+    let mut registry = TypeRegistry::new();
+    Inventory::metadata(&mut registry);
+    Account::metadata(&mut registry);
+    ExternalStatus::metadata(&mut registry);
+    InternalStatus::metadata(&mut registry);
+    AdjacentStatus::metadata(&mut registry);
+    UntaggedStatus::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new().with_type_guards(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options));
+}
+
+#[test]
+fn string_union_enums() {
+    let mut registry = TypeRegistry::new();
+    Nested::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_enum_style(go_away::TypeScriptEnumStyle::StringUnion);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options), @r###"
+    export type Nested = {
+        some_other_name: string;
+        an_int: number;
+        fulfilment_type: FulfilmentType;
+    }
+    export type FulfilmentType = "Delivery" | "Collection";
+    export const FulfilmentTypeValues = ["Delivery", "Collection"] as const;
+
+    export function isFulfilmentType(value: unknown): value is FulfilmentType {
+        return (FulfilmentTypeValues as readonly unknown[]).includes(value);
+    }
+
+    export function assertFulfilmentType(value: unknown): asserts value is FulfilmentType {
+        if (!isFulfilmentType(value)) {
+            throw new TypeError("value is not a valid FulfilmentType");
+        }
+    }
+    "###);
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_unset(value: &Option<String>) -> bool {
+    value.as_deref().map(str::is_empty).unwrap_or(true)
+}
+
+#[derive(TypeMetadata)]
+struct Profile {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(skip_serializing_if = "is_unset")]
+    bio: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "is_zero")]
+    visits: u32,
+    avatar: Option<String>,
+}
+
+#[test]
+fn optional_properties() {
+    let mut registry = TypeRegistry::new();
+    Profile::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_optional_properties(true)
+        .with_type_guards(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options));
+}
+
+#[test]
+fn readonly_interfaces() {
+    let mut registry = TypeRegistry::new();
+    Profile::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_struct_style(go_away::TypeScriptStructStyle::Interface)
+        .with_readonly_properties(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options), @r###"
+    export interface Profile {
+        readonly name: string;
+        readonly nickname: string | null;
+        readonly bio: string | null;
+        readonly tags: string[];
+        readonly visits: number;
+        readonly avatar: string | null;
+    }
+    "###);
+}
+
+#[derive(TypeMetadata)]
+struct OrderId(String);
+
+#[derive(TypeMetadata)]
+struct Quantity(Option<u32>);
+
+#[derive(TypeMetadata)]
+struct Order {
+    id: OrderId,
+    quantity: Quantity,
+}
+
+#[test]
+fn branded_newtypes() {
+    let mut registry = TypeRegistry::new();
+    Order::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_branded_newtypes(true)
+        .with_type_guards(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options));
+}
+
+#[derive(TypeMetadata)]
+struct WalletId(u64);
+
+#[derive(TypeMetadata)]
+struct Wallet {
+    id: WalletId,
+    balance: i64,
+    login_count: u32,
+    parent: Option<WalletId>,
+    transfers: Vec<i128>,
+    limits: std::collections::HashMap<u64, u64>,
+}
+
+#[test]
+fn bigint_wide_integers() {
+    let mut registry = TypeRegistry::new();
+    Wallet::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_wide_integers(go_away::TypeScriptWideIntegers::BigInt)
+        .with_type_guards(true);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options));
+}
+
+#[derive(TypeMetadata)]
+struct Transfer {
+    amount: u64,
+    note: String,
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalLedgerEntry {
+    Opened,
+    Transfer(Transfer),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentLedgerEntry {
+    Opened,
+    Adjusted(i64),
+}
+
+#[derive(TypeMetadata)]
+enum ExternalLedgerEntry {
+    Opened,
+    Batch(Vec<Transfer>),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum UntaggedLedgerEntry {
+    Note(String),
+    Transfer(Transfer),
+}
+
+#[test]
+fn bigint_revivers_for_unions() {
+    let mut registry = TypeRegistry::new();
+    InternalLedgerEntry::metadata(&mut registry);
+    AdjacentLedgerEntry::metadata(&mut registry);
+    ExternalLedgerEntry::metadata(&mut registry);
+    UntaggedLedgerEntry::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_wide_integers(go_away::TypeScriptWideIntegers::BigInt);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options));
+}
+
+#[test]
+fn string_wide_integers() {
+    let mut registry = TypeRegistry::new();
+    Wallet::metadata(&mut registry);
+
+    let options = go_away::TypeScriptOptions::new()
+        .with_wide_integers(go_away::TypeScriptWideIntegers::String);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options), @r###"
+    export type Wallet = {
+        id: WalletId;
+        balance: string;
+        login_count: number;
+        parent: WalletId | null;
+        transfers: string[];
+        limits: Record<string, string>;
+    }
+    export type WalletId = string;
+    "###);
+}
+
+#[test]
+fn warn_about_wide_integers() {
+    let mut registry = TypeRegistry::new();
+    Wallet::metadata(&mut registry);
+
+    let options =
+        go_away::TypeScriptOptions::new().with_wide_integers(go_away::TypeScriptWideIntegers::Warn);
+
+    assert_snapshot!(go_away::registry_to_output_with_options::<
+        go_away::TypeScriptType,
+    >(&registry, &options), @r###"
+    // warning: Wallet.balance holds 64 bit integers, which lose precision in typescript when they're above 2^53
+    // warning: Wallet.transfers holds 128 bit integers, which lose precision in typescript when they're above 2^53
+    // warning: Wallet.limits holds 64 bit integers, which lose precision in typescript when they're above 2^53
+    // warning: WalletId holds 64 bit integers, which lose precision in typescript when they're above 2^53
+
+    export type Wallet = {
+        id: WalletId;
+        balance: number;
+        login_count: number;
+        parent: WalletId | null;
+        transfers: number[];
+        limits: Record<number, number>;
+    }
+    export type WalletId = number;
+    "###);
+}
+
+#[cfg(feature = "rust_decimal")]
+#[derive(TypeMetadata)]
+struct Prices {
+    price: rust_decimal::Decimal,
+    #[go_away(as = "f64")]
+    float_price: rust_decimal::Decimal,
+}
+
+#[cfg(all(feature = "rust_decimal", not(feature = "rust_decimal-serde-float")))]
+#[test]
+fn decimals_are_strings() {
+    let mut registry = TypeRegistry::new();
+    Prices::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ), @r###"
+    export type Prices = {
+        price: string;
+        float_price: number;
+    }
+    "###);
+}
+
+#[cfg(feature = "rust_decimal-serde-float")]
+#[test]
+fn decimals_are_floats_with_serde_float() {
+    let mut registry = TypeRegistry::new();
+    Prices::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::TypeScriptType>(
+        &registry
+    ), @r###"
+    export type Prices = {
+        price: number;
+        float_price: number;
+    }
+    "###);
+}
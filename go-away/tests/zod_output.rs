@@ -0,0 +1,146 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use insta::assert_snapshot;
+
+use go_away::{TypeAlias, TypeMetadata, TypeRegistry};
+
+#[derive(TypeMetadata)]
+struct MyData {
+    field_one: String,
+    nested: Nested,
+    #[serde(default)]
+    retries: i32,
+    comment: Option<String>,
+}
+
+#[derive(TypeMetadata)]
+struct Nested {
+    #[serde(rename = "some_other_name")]
+    a_string: String,
+    an_int: i64,
+    fulfilment_type: FulfilmentType,
+}
+
+#[derive(TypeMetadata)]
+enum FulfilmentType {
+    Delivery,
+    Collection,
+}
+
+#[test]
+fn struct_output() {
+    let mut registry = TypeRegistry::new();
+    MyData::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[serde(default)]
+struct Settings {
+    theme: String,
+    per_page: Option<u32>,
+    limits: HashMap<FulfilmentType, Vec<f64>>,
+}
+
+#[test]
+fn container_defaults() {
+    let mut registry = TypeRegistry::new();
+    Settings::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct Failure {
+    reason: String,
+}
+
+#[derive(TypeMetadata)]
+enum ExternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type")]
+enum InternalStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "type", content = "data")]
+enum AdjacentStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[derive(TypeMetadata)]
+#[serde(untagged)]
+enum UntaggedStatus {
+    Pending,
+    Failed(Failure),
+}
+
+#[test]
+fn union_representations() {
+    let mut registry = TypeRegistry::new();
+    ExternalStatus::metadata(&mut registry);
+    InternalStatus::metadata(&mut registry);
+    AdjacentStatus::metadata(&mut registry);
+    UntaggedStatus::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "event")]
+enum StatusEvent {
+    Changed(InternalStatus),
+    Failed(Failure),
+}
+
+#[test]
+fn internally_tagged_nested_union() {
+    let mut registry = TypeRegistry::new();
+    StatusEvent::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry));
+}
+
+#[derive(TypeMetadata)]
+#[serde(tag = "op")]
+enum Expr {
+    Literal { value: f64 },
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+#[test]
+fn recursive_types() {
+    let mut registry = TypeRegistry::new();
+    Expr::metadata(&mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry));
+}
+
+#[derive(TypeMetadata)]
+struct UserId(String);
+
+#[test]
+fn newtypes_and_aliases() {
+    type Users = HashMap<String, UserId>;
+
+    let mut registry = TypeRegistry::new();
+    Users::register_alias("Users", &mut registry);
+
+    assert_snapshot!(go_away::registry_to_output::<go_away::ZodSchema>(&registry), @r###"
+    export const UserIdSchema = z.string();
+    export type UserId = z.infer<typeof UserIdSchema>;
+
+    export const UsersSchema = z.record(z.string(), z.lazy(() => UserIdSchema));
+    export type Users = z.infer<typeof UsersSchema>;
+    "###);
+}